@@ -1,38 +1,119 @@
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use fuzzy_filter::FuzzyFilter;
-use std::error::Error;
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap, fmt::Debug, fs, future::Future, path::PathBuf, str::FromStr,
+    time::Duration,
+};
 use twitch_api2::{
     helix::{
-        channels::{ModifyChannelInformationBody, ModifyChannelInformationRequest},
+        channels::{
+            ChannelInformation, GetChannelInformationRequest, StartCommercialBody,
+            StartCommercialRequest,
+        },
         points::{
             CreateCustomRewardBody, CreateCustomRewardRequest, CustomReward,
-            GetCustomRewardRequest, UpdateCustomRewardBody, UpdateCustomRewardRequest,
+            CustomRewardRedemption, CustomRewardRedemptionStatus, DeleteCustomRewardRequest,
+            GetCustomRewardRedemptionRequest, GetCustomRewardRequest, UpdateCustomRewardBody,
+            UpdateCustomRewardRequest, UpdateRedemptionStatusBody, UpdateRedemptionStatusRequest,
         },
         search::{search_categories::Category, SearchCategoriesRequest},
         streams::{
             get_stream_tags::GetStreamTagsRequest,
-            replace_stream_tags::{
-                ReplaceStreamTags, ReplaceStreamTagsBody, ReplaceStreamTagsRequest,
-            },
+            replace_stream_tags::{ReplaceStreamTagsBody, ReplaceStreamTagsRequest},
         },
         tags::{AutoGenerated, GetAllStreamTagsRequest, TwitchTag},
         users::{GetUsersRequest, User},
+        Request, RequestDelete, RequestGet, RequestPatch, RequestPost, RequestPut, Response,
+    },
+    twitch_oauth2::{
+        AccessToken, AppAccessToken, ClientId, ClientSecret, RefreshToken, Scope, TwitchToken,
+        UserToken, ValidationError,
+    },
+    types::{
+        BroadcasterType, CategoryId, CommercialLength, Nickname, RedemptionId, RewardId, TagId,
+        UserId,
     },
-    twitch_oauth2::{AccessToken, TwitchToken, UserToken},
-    types::{CategoryId, Nickname, RewardId, TagId, UserId},
     HelixClient,
 };
-use twitch_oauth2::client::surf_http_client;
 
 use derivative::Derivative;
 use derive_builder::Builder;
+use tokio::sync::{Mutex, RwLock, RwLockReadGuard, Semaphore};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 
+use crate::error::TwitchCtlError;
 use crate::{exit, warning};
 
-#[derive(thiserror::Error, Debug)]
-enum ApiError {
-    #[error("No user with login `{0}` found.")]
-    NoUser(Nickname),
-}
+mod bits;
+mod builder;
+mod cache;
+mod channels;
+mod chat;
+mod clips;
+mod emotes;
+#[cfg(feature = "eventsub")]
+mod eventsub;
+mod extensions;
+mod games;
+mod helix;
+#[cfg(feature = "test-util")]
+mod mock;
+mod moderation;
+mod operation;
+mod polls;
+mod predictions;
+mod raids;
+mod retry;
+mod schedule;
+mod streams;
+mod subscriptions;
+mod sync;
+#[macro_use]
+mod token;
+mod trace;
+mod transport;
+mod twitch_api;
+mod users;
+mod videos;
+
+pub use bits::{Cheermote, CheermoteImageSet, CheermoteImages, CheermoteTier};
+pub use builder::ApiClientBuilder;
+use cache::ResponseCache;
+pub use channels::{ChannelFollowers, ChannelSearchResult, CreatorGoal, Follower};
+pub use chat::{AnnouncementColor, ChatSettings, ChatSettingsPatch};
+pub use clips::{Clip, CreatedClip};
+pub use emotes::{Emote, EmoteImages};
+#[cfg(feature = "eventsub")]
+pub use eventsub::EventSubClient;
+pub use extensions::{ActiveExtensions, ExtensionSlot};
+use helix::BaseUrl;
+pub use games::{box_art_url, MAX_BOX_ART_SIZE};
+pub use helix::{Page, Paged};
+#[cfg(feature = "test-util")]
+pub use mock::{MockApiClient, MockCall};
+pub use moderation::{
+    AutoModResult, AutoModSettings, AutoModSettingsPatch, BannedUser, BlockedTerm,
+    MAX_TIMEOUT_SECONDS, ModeratedChannel, ShieldModeStatus,
+};
+pub use operation::Operation;
+pub use polls::{Poll, PollChoice, PollEnd};
+pub use predictions::{Prediction, PredictionOutcome, PredictionStatus};
+pub use raids::Raid;
+use retry::RateLimitTracker;
+pub use retry::{RateLimitStatus, RetryPolicy};
+pub use schedule::{NewScheduleSegment, ScheduleCategory, ScheduleSegment, ScheduleSegmentPatch};
+pub use streams::{HypeTrain, StreamMarker, StreamStatus};
+pub use subscriptions::{ChannelSubscriptions, Subscriber};
+pub use sync::SyncReport;
+use token::ClientToken;
+pub use token::TokenStatus;
+pub use transport::HttpClient;
+use transport::{oauth_http_client, Transport};
+pub use twitch_api::TwitchApi;
+pub use users::{BlockContext, BlockReason, BlockedUser};
+pub use videos::{Video, VideoType};
 
 pub enum UserIdent {
     UserName(Nickname),
@@ -40,77 +121,772 @@ pub enum UserIdent {
     None,
 }
 
-async fn get_user(token_string: &str) -> Result<UserToken, Box<dyn Error + 'static>> {
-    let token = UserToken::from_existing(
-        surf_http_client,
-        AccessToken::new(token_string.to_string()),
-        None,
-        None,
-    )
-    .await?;
-    token.validate_token(surf_http_client).await?;
+/// How the category names returned by a search are matched against the search term
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CategoryMatch {
+    /// every result of the Twitch search
+    Contains,
+    /// names starting with the term, ignoring case
+    StartsWith,
+    /// names equal to the term, ignoring case
+    Exact,
+}
+
+impl CategoryMatch {
+    fn matches(&self, term: &str, name: &str) -> bool {
+        match self {
+            CategoryMatch::Contains => true,
+            CategoryMatch::StartsWith => name.to_lowercase().starts_with(&term.to_lowercase()),
+            CategoryMatch::Exact => name.to_lowercase() == term.to_lowercase(),
+        }
+    }
+}
+
+impl Default for CategoryMatch {
+    fn default() -> Self {
+        CategoryMatch::Contains
+    }
+}
+
+impl FromStr for CategoryMatch {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "contains" => Ok(CategoryMatch::Contains),
+            "starts-with" => Ok(CategoryMatch::StartsWith),
+            "exact" => Ok(CategoryMatch::Exact),
+            _ => Err("unsupported match mode"),
+        }
+    }
+}
+
+/// The result of looking up a reward by its title
+#[derive(Debug, Clone)]
+pub enum RewardLookup {
+    Found(CustomReward),
+    NotFound,
+    /// the matching rewards, the closest matches first
+    Ambiguous(Vec<CustomReward>),
+}
+
+/// The locale of tag names if the client is not given another one
+pub const DEFAULT_LOCALE: &str = "en-us";
+
+/// The number of characters Twitch allows in a stream title
+pub const MAX_TITLE_LENGTH: usize = 140;
+
+/// How many requests a client sends at the same time by default
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 4;
+
+/// How long a request to Twitch can take by default
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The scopes needed to manage the stream information and rewards
+pub const DEFAULT_REQUIRED_SCOPES: &[Scope] = &[
+    Scope::ChannelManageBroadcast,
+    Scope::ChannelManageRedemptions,
+];
+
+/// Where to load the access token from
+#[derive(Debug, Clone)]
+pub enum TokenSource {
+    Raw(String),
+    /// the name of an environment variable, which can also be set in a .env file
+    EnvVar(String),
+    /// a file only containing the token
+    File(PathBuf),
+}
+
+impl TokenSource {
+    fn resolve(self) -> Result<String, TwitchCtlError> {
+        let token = match self {
+            TokenSource::Raw(token) => token,
+            TokenSource::EnvVar(var) => dotenv::var(&var).map_err(|_| {
+                TwitchCtlError::MissingToken(format!(
+                    "{} should exist in .env or your env vars.",
+                    var
+                ))
+            })?,
+            TokenSource::File(path) => fs::read_to_string(&path)
+                .map_err(|e| {
+                    TwitchCtlError::MissingToken(format!(
+                        "Unable to read token file `{}`: {}",
+                        path.display(),
+                        e
+                    ))
+                })?
+                .trim_end()
+                .to_string(),
+        };
+        if token.is_empty() {
+            Err(TwitchCtlError::MissingToken(
+                "The token is empty.".to_string(),
+            ))
+        } else {
+            Ok(token)
+        }
+    }
+}
+
+/// What a token is trusted to be when it is not validated with Twitch
+pub(crate) struct UncheckedToken {
+    pub client_id: String,
+    pub login: String,
+    pub user_id: String,
+    pub scopes: Vec<Scope>,
+}
+
+/// Credentials used to refresh an expired access token
+pub struct TokenRefresh {
+    pub refresh_token: String,
+    /// the client id the token was generated for
+    pub client_id: String,
+    pub client_secret: String,
+    /// called with the new access token and refresh token every time it is refreshed,
+    /// so they can be written back to where they were loaded from
+    pub on_refresh: Box<dyn Fn(&str, &str) + Send + Sync>,
+}
+
+async fn get_user(
+    token_string: &str,
+    refresh: Option<&TokenRefresh>,
+) -> Result<UserToken, TwitchCtlError> {
+    let refresh_token = refresh.map(|r| RefreshToken::new(r.refresh_token.clone()));
+    let client_secret = refresh.map(|r| ClientSecret::new(r.client_secret.clone()));
+
+    let token = retry_validation(|| {
+        UserToken::from_existing(
+            oauth_http_client,
+            AccessToken::new(token_string.to_string()),
+            refresh_token.clone(),
+            client_secret.clone(),
+        )
+    })
+    .await;
+    let token = match (token, refresh) {
+        // the access token expired, try to get a new one before giving up
+        (Err(ValidationError::NotAuthorized), Some(refresh)) => {
+            let (access_token, _, new_refresh_token) = twitch_api2::twitch_oauth2::refresh_token(
+                oauth_http_client,
+                &RefreshToken::new(refresh.refresh_token.clone()),
+                &ClientId::new(refresh.client_id.clone()),
+                &ClientSecret::new(refresh.client_secret.clone()),
+            )
+            .await?;
+            // Twitch can hand out a new refresh token, which replaces the old one
+            let refresh_token = new_refresh_token.or(refresh_token);
+            (refresh.on_refresh)(
+                access_token.secret(),
+                refresh_token
+                    .as_ref()
+                    .map_or(refresh.refresh_token.as_str(), |token| token.secret()),
+            );
+            UserToken::from_existing(
+                oauth_http_client,
+                access_token,
+                refresh_token,
+                client_secret,
+            )
+            .await?
+        }
+        (token, _) => token?,
+    };
+    retry_validation(|| token.validate_token(oauth_http_client)).await?;
 
     Ok(token)
 }
 
+/// How often a failed token validation is retried, unless Twitch rejected the token
+const VALIDATION_RETRIES: u32 = 3;
+
+/// Retries `validate` with exponential backoff while Twitch or the connection fails.
+async fn retry_validation<T, RE, F, Fut>(mut validate: F) -> Result<T, ValidationError<RE>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ValidationError<RE>>>,
+{
+    let mut delay = Duration::from_millis(500);
+    for _ in 0..VALIDATION_RETRIES {
+        match validate().await {
+            Err(ValidationError::NotAuthorized) => return Err(ValidationError::NotAuthorized),
+            Err(_) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            res => return res,
+        }
+    }
+    validate().await
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct ApiClient<'a> {
     #[derivative(Debug = "ignore")]
-    helix_client: HelixClient<'a, surf::Client>,
-    token: UserToken,
-    user: UserId,
+    http_client: Transport,
+    #[derivative(Debug = "ignore")]
+    helix_client: HelixClient<'a, Transport>,
+    token: RwLock<ClientToken>,
+    #[derivative(Debug = "ignore")]
+    on_refresh: Option<Box<dyn Fn(&str, &str) + Send + Sync>>,
+    /// the login and id of the token user, `None` for app access tokens
+    login: Option<String>,
+    user: Option<UserId>,
+    scopes: Vec<Scope>,
+    #[derivative(Debug = "ignore")]
+    tags_cache: Mutex<Option<Vec<TwitchTag>>>,
+    #[derivative(Debug = "ignore")]
+    whoami_cache: Mutex<Option<User>>,
+    /// the ids of the logins resolved so far, by lowercase login
+    #[derivative(Debug = "ignore")]
+    user_ids: std::sync::Mutex<HashMap<Nickname, UserId>>,
+    retry_policy: RetryPolicy,
+    rate_limit: RateLimitTracker,
+    /// limits the requests sent at the same time to `max_in_flight`
+    in_flight: Semaphore,
+    max_in_flight: usize,
+    base_url: BaseUrl,
+    request_timeout: Option<Duration>,
+    #[derivative(Debug = "ignore")]
+    cache: Option<ResponseCache>,
+    /// the locale of tag names when none is given
+    default_locale: String,
+    /// asked before destructive actions, see [`ApiClient::with_confirm`]
+    #[derivative(Debug = "ignore")]
+    confirm: Option<Box<dyn Fn(&str) -> bool + Send + Sync>>,
+    dry_run: bool,
 }
 
 impl<'a> ApiClient<'a> {
-    pub async fn new(token: &str) -> Result<ApiClient<'a>, Box<dyn Error>> {
-        let token = get_user(token).await?;
-        Ok(ApiClient {
-            helix_client: HelixClient::with_client(surf::Client::new()),
-            token: token.clone(),
-            user: token.user_id.into(),
+    /// Creates a client for the given access token.
+    ///
+    /// When `refresh` is set, the token is refreshed automatically once it expires.
+    ///
+    /// Fails if the token is missing any of the [`DEFAULT_REQUIRED_SCOPES`].
+    pub async fn new(
+        token: &str,
+        refresh: Option<TokenRefresh>,
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        Self::builder(TokenSource::Raw(token.to_string()), refresh)
+            .build()
+            .await
+    }
+
+    /// Starts building a client with settings other than the defaults.
+    pub fn builder(token: TokenSource, refresh: Option<TokenRefresh>) -> ApiClientBuilder {
+        let builder = ApiClientBuilder::new(token);
+        match refresh {
+            Some(refresh) => builder.refresh(refresh),
+            None => builder,
+        }
+    }
+
+    /// Like [`ApiClient::new`], but only requires the token to have `required_scopes`.
+    pub async fn with_required_scopes(
+        token: &str,
+        refresh: Option<TokenRefresh>,
+        required_scopes: &[Scope],
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        Self::builder(TokenSource::Raw(token.to_string()), refresh)
+            .required_scopes(required_scopes)
+            .build()
+            .await
+    }
+
+    /// Like [`ApiClient::new`], but sends the requests with `http_client`.
+    ///
+    /// Clients created from clones of the same [`HttpClient`] share its connection pool.
+    pub async fn with_http_client(
+        token: &str,
+        refresh: Option<TokenRefresh>,
+        http_client: HttpClient,
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        Self::builder(TokenSource::Raw(token.to_string()), refresh)
+            .http_client(http_client)
+            .build()
+            .await
+    }
+
+    async fn connect(
+        token: &str,
+        refresh: Option<TokenRefresh>,
+        required_scopes: &[Scope],
+        http_client: HttpClient,
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        let token = get_user(token, refresh.as_ref()).await?;
+        Self::from_user_token(token, refresh, required_scopes, http_client)
+    }
+
+    /// Creates a client for a token without validating it with Twitch.
+    ///
+    /// The token is trusted to belong to `login` with the id `user_id` and to have `scopes`.
+    fn connect_unchecked(
+        token: &str,
+        refresh: Option<TokenRefresh>,
+        required_scopes: &[Scope],
+        http_client: HttpClient,
+        unchecked: UncheckedToken,
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        let token = UserToken::from_existing_unchecked(
+            AccessToken::new(token.to_string()),
+            refresh
+                .as_ref()
+                .map(|r| RefreshToken::new(r.refresh_token.clone())),
+            ClientId::new(unchecked.client_id),
+            refresh
+                .as_ref()
+                .map(|r| ClientSecret::new(r.client_secret.clone())),
+            unchecked.login,
+            unchecked.user_id,
+            Some(unchecked.scopes),
+            None,
+        );
+        Self::from_user_token(token, refresh, required_scopes, http_client)
+    }
+
+    fn from_user_token(
+        token: UserToken,
+        refresh: Option<TokenRefresh>,
+        required_scopes: &[Scope],
+        http_client: HttpClient,
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        let missing: Vec<Scope> = required_scopes
+            .iter()
+            .filter(|scope| !token.scopes().contains(scope))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            return Err(TwitchCtlError::MissingScopes(missing));
+        }
+        let login = token.login.to_string();
+        let user = token.user_id.clone().into();
+        Ok(Self::from_token(
+            ClientToken::User(token),
+            Some(login),
+            Some(user),
+            refresh.map(|r| r.on_refresh),
+            http_client,
+        ))
+    }
+
+    /// Creates a client authorized as the application instead of a user.
+    ///
+    /// Such a client can only read public data like categories or users,
+    /// everything acting on behalf of the token user fails with
+    /// [`TwitchCtlError::UserTokenRequired`].
+    pub async fn with_app_token(
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        let token = AppAccessToken::get_app_access_token(
+            oauth_http_client,
+            ClientId::new(client_id.to_string()),
+            ClientSecret::new(client_secret.to_string()),
+            vec![],
+        )
+        .await
+        .map_err(|e| TwitchCtlError::Auth(e.to_string()))?;
+        Ok(Self::from_token(
+            ClientToken::App(token),
+            None,
+            None,
+            None,
+            HttpClient::new(),
+        ))
+    }
+
+    fn from_token(
+        token: ClientToken,
+        login: Option<String>,
+        user: Option<UserId>,
+        on_refresh: Option<Box<dyn Fn(&str, &str) + Send + Sync>>,
+        http_client: HttpClient,
+    ) -> ApiClient<'a> {
+        let scopes = with_token!(&token, |token| token.scopes().to_vec());
+        let rate_limit = RateLimitTracker::default();
+        let base_url = BaseUrl::default();
+        let http_client = Transport::new(http_client, base_url.clone(), rate_limit.clone());
+        ApiClient {
+            helix_client: HelixClient::with_client(http_client.clone()),
+            http_client,
+            login,
+            user,
+            scopes,
+            token: RwLock::new(token),
+            on_refresh,
+            tags_cache: Mutex::new(None),
+            whoami_cache: Mutex::new(None),
+            user_ids: std::sync::Mutex::new(HashMap::new()),
+            retry_policy: RetryPolicy::default(),
+            rate_limit,
+            in_flight: Semaphore::new(DEFAULT_MAX_IN_FLIGHT),
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            base_url,
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+            cache: None,
+            default_locale: DEFAULT_LOCALE.to_string(),
+            confirm: None,
+            dry_run: false,
+        }
+    }
+
+    /// Creates a client with the token loaded from `source`.
+    pub async fn from_source(
+        source: TokenSource,
+        refresh: Option<TokenRefresh>,
+        required_scopes: &[Scope],
+    ) -> Result<ApiClient<'a>, TwitchCtlError> {
+        Self::builder(source, refresh)
+            .required_scopes(required_scopes)
+            .build()
+            .await
+    }
+
+    /// Refreshes the access token if it expired and refresh credentials were provided.
+    ///
+    /// App access tokens can always be renewed.
+    pub async fn refresh_if_needed(&self) -> Result<(), TwitchCtlError> {
+        let can_refresh = self.on_refresh.is_some() || self.user.is_none();
+        if !can_refresh || self.token.read().await.expires_in().as_secs() > 0 {
+            return Ok(());
+        }
+        let mut token = self.token.write().await;
+        // another request could have refreshed it while we were waiting for the lock
+        if token.expires_in().as_secs() == 0 {
+            token.refresh().await?;
+            if let (Some(on_refresh), Some(refresh_token)) =
+                (&self.on_refresh, token.refresh_token())
+            {
+                on_refresh(token.access_token().secret(), refresh_token.secret());
+            }
+        }
+        Ok(())
+    }
+
+    /// Only logs requests that would change something instead of sending them.
+    ///
+    /// Requests reading data are still sent.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Limits how long a request can take before it fails with [`TwitchCtlError::Timeout`].
+    ///
+    /// Defaults to [`DEFAULT_REQUEST_TIMEOUT`], `None` waits forever.
+    pub fn with_request_timeout(mut self, request_timeout: Option<Duration>) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Sets the locale of tag names used when a method is not given one, defaults to
+    /// [`DEFAULT_LOCALE`].
+    pub fn with_default_locale(mut self, locale: &str) -> Self {
+        self.default_locale = locale.to_string();
+        self
+    }
+
+    /// Asks `confirm` before deleting something, with a description like
+    /// `Delete the reward 1234?`.
+    ///
+    /// If it returns `false` the action fails with [`TwitchCtlError::Cancelled`]. In a dry run
+    /// nothing is deleted, so it is not asked.
+    pub fn with_confirm<F>(mut self, confirm: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.confirm = Some(Box::new(confirm));
+        self
+    }
+
+    /// Fails with [`TwitchCtlError::Cancelled`] if the confirmation hook declines `action`.
+    pub(crate) fn confirmed(&self, action: &str) -> Result<(), TwitchCtlError> {
+        match &self.confirm {
+            Some(confirm) if !self.dry_run && !confirm(action) => Err(TwitchCtlError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    fn log_dry_run<B: Serialize>(
+        &self,
+        method: &str,
+        path: &str,
+        req: &dyn Debug,
+        body: Option<&B>,
+    ) {
+        match body.map(serde_json::to_string) {
+            Some(Ok(body)) => warning!(
+                "Dry run, not sending {} {} {:?} {}",
+                method,
+                path,
+                req,
+                body
+            ),
+            _ => warning!("Dry run, not sending {} {} {:?}", method, path, req),
+        }
+    }
+
+    async fn req_get<R, D>(&self, op: Operation, req: R) -> Result<Response<R, D>, TwitchCtlError>
+    where
+        R: Request<Response = D> + RequestGet + Clone + Serialize,
+        D: DeserializeOwned + PartialEq,
+    {
+        let req = &req;
+        self.retrying(op, move || {
+            trace::traced(R::PATH, trace::broadcaster_id(req), async move {
+                let token = self.token().await?;
+                Ok(with_token!(&*token, |token| self
+                    .helix_client
+                    .req_get(req.clone(), token)
+                    .await?))
+            })
+        })
+        .await
+    }
+
+    async fn req_post<R, B, D>(
+        &self,
+        op: Operation,
+        req: R,
+        body: B,
+    ) -> Result<Option<Response<R, D>>, TwitchCtlError>
+    where
+        R: Request<Response = D> + RequestPost<Body = B> + Clone + Serialize + Debug,
+        B: Serialize + Clone,
+        D: DeserializeOwned + PartialEq,
+    {
+        self.get_user_id()?;
+        if self.dry_run {
+            self.log_dry_run("POST", R::PATH, &req, Some(&body));
+            return Ok(None);
+        }
+        let (req, body) = (&req, &body);
+        self.retrying(op, move || {
+            trace::traced(R::PATH, trace::broadcaster_id(req), async move {
+                let token = self.token().await?;
+                Ok(with_token!(&*token, |token| self
+                    .helix_client
+                    .req_post(req.clone(), body.clone(), token)
+                    .await?))
+            })
+        })
+        .await
+        .map(Some)
+    }
+
+    async fn req_patch<R, B, D>(
+        &self,
+        op: Operation,
+        req: R,
+        body: B,
+    ) -> Result<Option<Response<R, D>>, TwitchCtlError>
+    where
+        R: Request<Response = D> + RequestPatch<Body = B> + Clone + Serialize + Debug,
+        B: Serialize + Clone,
+        D: DeserializeOwned + PartialEq,
+    {
+        self.get_user_id()?;
+        if self.dry_run {
+            self.log_dry_run("PATCH", R::PATH, &req, Some(&body));
+            return Ok(None);
+        }
+        let (req, body) = (&req, &body);
+        self.retrying(op, move || {
+            trace::traced(R::PATH, trace::broadcaster_id(req), async move {
+                let token = self.token().await?;
+                Ok(with_token!(&*token, |token| self
+                    .helix_client
+                    .req_patch(req.clone(), body.clone(), token)
+                    .await?))
+            })
+        })
+        .await
+        .map(Some)
+    }
+
+    async fn req_put<R, B, D>(
+        &self,
+        op: Operation,
+        req: R,
+        body: B,
+    ) -> Result<Option<Response<R, D>>, TwitchCtlError>
+    where
+        R: Request<Response = D> + RequestPut<Body = B> + Clone + Serialize + Debug,
+        B: Serialize + Clone,
+        D: DeserializeOwned + PartialEq,
+    {
+        self.get_user_id()?;
+        if self.dry_run {
+            self.log_dry_run("PUT", R::PATH, &req, Some(&body));
+            return Ok(None);
+        }
+        let (req, body) = (&req, &body);
+        self.retrying(op, move || {
+            trace::traced(R::PATH, trace::broadcaster_id(req), async move {
+                let token = self.token().await?;
+                Ok(with_token!(&*token, |token| self
+                    .helix_client
+                    .req_put(req.clone(), body.clone(), token)
+                    .await?))
+            })
+        })
+        .await
+        .map(Some)
+    }
+
+    async fn req_delete<R, D>(
+        &self,
+        op: Operation,
+        req: R,
+    ) -> Result<Option<Response<R, D>>, TwitchCtlError>
+    where
+        R: Request<Response = D> + RequestDelete + Clone + Serialize + Debug,
+        D: DeserializeOwned + PartialEq,
+    {
+        self.get_user_id()?;
+        if self.dry_run {
+            self.log_dry_run("DELETE", R::PATH, &req, helix::NO_BODY);
+            return Ok(None);
+        }
+        let req = &req;
+        self.retrying(op, move || {
+            trace::traced(R::PATH, trace::broadcaster_id(req), async move {
+                let token = self.token().await?;
+                Ok(with_token!(&*token, |token| self
+                    .helix_client
+                    .req_delete(req.clone(), token)
+                    .await?))
+            })
         })
+        .await
+        .map(Some)
     }
 
-    pub fn get_user(&self) -> &str {
-        self.token.login.as_ref()
+    /// Returns the current token, refreshing it first if needed.
+    async fn token(&self) -> Result<RwLockReadGuard<'_, ClientToken>, TwitchCtlError> {
+        self.refresh_if_needed().await?;
+        Ok(self.token.read().await)
     }
 
-    pub fn get_user_id(&self) -> &UserId {
-        &self.user
+    /// The login of the token user, fails for app access tokens
+    pub fn get_user(&self) -> Result<&str, TwitchCtlError> {
+        self.login
+            .as_deref()
+            .ok_or(TwitchCtlError::UserTokenRequired)
+    }
+
+    /// The id of the token user, fails for app access tokens
+    pub fn get_user_id(&self) -> Result<&UserId, TwitchCtlError> {
+        self.user.as_ref().ok_or(TwitchCtlError::UserTokenRequired)
     }
 
     pub async fn search_categories(
         &self,
         term: &str,
         max: usize,
-    ) -> Result<Option<Vec<Category>>, Box<dyn Error>> {
-        // TODO Implement some better filter (only starting with for example) to reduce the number
-        // of results for searches
-
-        let req = SearchCategoriesRequest::builder()
-            .query(term)
-            .first(max.max(1).min(100).to_string())
-            .build();
-        let res: Vec<Category> = self.helix_client.req_get(req, &self.token).await?.data;
-        if res.len() > 0 {
-            Ok(Some(res))
-        } else {
-            Ok(None)
-        }
+        match_mode: CategoryMatch,
+    ) -> Result<Option<Vec<Category>>, TwitchCtlError> {
+        let key = format!(
+            "search/categories?query={}&max={}&match={:?}",
+            term, max, match_mode
+        );
+        self.cached(key, || async {
+            // Filtering happens after the request, so get as many candidates as possible
+            let first = match match_mode {
+                CategoryMatch::Contains => max.max(1).min(100),
+                _ => 100,
+            };
+            let req = SearchCategoriesRequest::builder()
+                .query(term)
+                .first(first.to_string())
+                .build();
+            let res: Vec<Category> = self
+                .req_get(Operation::SearchCategories, req)
+                .await?
+                .data
+                .into_iter()
+                .filter(|category| match_mode.matches(term, &category.name))
+                .take(max.max(1))
+                .collect();
+            if res.len() > 0 {
+                Ok(Some(res))
+            } else {
+                Ok(None)
+            }
+        })
+        .await
     }
-    pub async fn search_category(&self, term: &str) -> Result<Option<Category>, Box<dyn Error>> {
-        match self.search_categories(term, 1).await? {
+    pub async fn search_category(&self, term: &str) -> Result<Option<Category>, TwitchCtlError> {
+        match self
+            .search_categories(term, 1, CategoryMatch::Contains)
+            .await?
+        {
             Some(cs) => Ok(Some(cs[0].clone())),
             None => Ok(None),
         }
     }
 
+    /// Returns up to `max` categories with names close to `name`, the closest first.
+    ///
+    /// Meant for suggesting a category when none is named `name`, e.g. `Minecraft` for
+    /// `Minecaft`.
+    pub async fn suggest_categories(
+        &self,
+        name: &str,
+        max: usize,
+    ) -> Result<Vec<Category>, TwitchCtlError> {
+        let mut candidates = self
+            .search_categories(name, 100, CategoryMatch::Contains)
+            .await?
+            .unwrap_or_default();
+        // Twitch does not find anything for some typos, the start of the name finds more
+        let prefix: String = name.chars().take(3).collect();
+        if candidates.is_empty() && prefix.len() < name.len() {
+            candidates = self
+                .search_categories(&prefix, 100, CategoryMatch::Contains)
+                .await?
+                .unwrap_or_default();
+        }
+        let name = normalize_title(name);
+        candidates
+            .sort_by_cached_key(|category| edit_distance(&name, &normalize_title(&category.name)));
+        candidates.truncate(max);
+        Ok(candidates)
+    }
+
+    /// Returns the id of the category named `name`, ignoring case.
+    ///
+    /// Unlike [`ApiClient::search_category`] this does not fall back to similar names.
+    pub async fn resolve_category_exact(
+        &self,
+        name: &str,
+    ) -> Result<Option<CategoryId>, TwitchCtlError> {
+        Ok(self
+            .search_categories(name, 1, CategoryMatch::Exact)
+            .await?
+            .map(|mut categories| categories.remove(0).id))
+    }
+
+    /// Returns the token user, fetched only once per client.
+    pub async fn whoami(&self) -> Result<User, TwitchCtlError> {
+        let user_id = self.get_user_id()?;
+        let mut cache = self.whoami_cache.lock().await;
+        if let Some(user) = &*cache {
+            return Ok(user.clone());
+        }
+        let user = self.get_user_by_id(user_id).await?.ok_or_else(|| {
+            TwitchCtlError::NotFound("The token user does not exist.".to_string())
+        })?;
+        *cache = Some(user.clone());
+        Ok(user)
+    }
+
     pub async fn get_users(
         &self,
         user_names: &[&Nickname],
         user_ids: &[&UserId],
-    ) -> Result<Vec<User>, Box<dyn Error>> {
+    ) -> Result<Vec<User>, TwitchCtlError> {
         let user_names: Vec<Nickname> = user_names.iter().cloned().cloned().collect();
         let user_ids: Vec<UserId> = user_ids.iter().cloned().cloned().collect();
         let req = match (user_names.len(), user_ids.len()) {
@@ -123,32 +899,126 @@ impl<'a> ApiClient<'a> {
                 .build(),
         };
 
-        let res: Vec<User> = self.helix_client.req_get(req, &self.token).await?.data;
+        let res: Vec<User> = self.req_get(Operation::GetUsers, req).await?.data;
+        let mut user_ids = self.user_ids.lock().expect("lock is not poisoned");
+        for user in &res {
+            user_ids.insert(login_key(user.login.as_str()), user.id.clone());
+        }
         Ok(res)
     }
 
+    /// Returns the id of `login` if it was resolved before.
+    fn cached_user_id(&self, login: &str) -> Option<UserId> {
+        self.user_ids
+            .lock()
+            .expect("lock is not poisoned")
+            .get(&login_key(login))
+            .cloned()
+    }
+
+    /// Forgets the ids of the logins resolved so far, e.g. after a user was renamed.
+    ///
+    /// Logins are only looked up once per client otherwise.
+    pub fn clear_user_cache(&self) {
+        self.user_ids.lock().expect("lock is not poisoned").clear();
+    }
+
+    /// Returns the user with the login `login`, or `None` if there is none.
+    pub async fn get_user_by_login(
+        &self,
+        login: &Nickname,
+    ) -> Result<Option<User>, TwitchCtlError> {
+        Ok(self.get_users(&[login], &[]).await?.into_iter().next())
+    }
+
+    /// Returns the user with the id `id`, or `None` if there is none.
+    pub async fn get_user_by_id(&self, id: &UserId) -> Result<Option<User>, TwitchCtlError> {
+        Ok(self.get_users(&[], &[id]).await?.into_iter().next())
+    }
+
+    /// Replaces the tags of a stream and returns the ids of the tags it has afterwards.
+    ///
+    /// Twitch does not answer with the tags, so they are fetched again, which includes the
+    /// automatic tags. In a dry run `tag_ids` are returned.
     pub async fn replace_stream_tags(
         &self,
         broadcaster_id: &UserId,
         tag_ids: Vec<TagId>,
-    ) -> Result<ReplaceStreamTags, Box<dyn Error + 'static>> {
+    ) -> Result<Vec<TagId>, TwitchCtlError> {
         let req = ReplaceStreamTagsRequest::builder()
             .broadcaster_id(broadcaster_id.clone())
             .build();
-        let body = ReplaceStreamTagsBody::builder().tag_ids(tag_ids).build();
-        let res = self.helix_client.req_put(req, body, &self.token).await?;
-        Ok(res.data)
+        let body = ReplaceStreamTagsBody::builder()
+            .tag_ids(tag_ids.clone())
+            .build();
+        match self
+            .req_put(Operation::ReplaceStreamTags, req, body)
+            .await?
+        {
+            Some(_) => Ok(self
+                .get_stream_tags(broadcaster_id)
+                .await?
+                .into_iter()
+                .map(|tag| tag.id)
+                .collect()),
+            None => Ok(tag_ids),
+        }
+    }
+
+    /// Replaces the tags of a stream with the tags named `tag_names` in `locale`, or in the
+    /// default locale of the client if it is `None`.
+    ///
+    /// Returns the names that did not match any tag.
+    pub async fn set_stream_tags_by_name(
+        &self,
+        broadcaster_id: &UserId,
+        tag_names: &[String],
+        locale: Option<&str>,
+    ) -> Result<Vec<String>, TwitchCtlError> {
+        let locale = locale.unwrap_or(&self.default_locale);
+        let (tag_ids, unmatched) = self
+            .resolve_tag_names(tag_names, Some(&[locale, "en-us"][..]))
+            .await?;
+        if tag_ids.len() > 5 {
+            return Err(TwitchCtlError::Validation(format!(
+                "A stream can have at most 5 tags, got {}.",
+                tag_ids.len()
+            )));
+        }
+        self.replace_stream_tags(broadcaster_id, tag_ids).await?;
+        Ok(unmatched)
     }
 
-    pub async fn get_stream_tags(&self, id: &UserId) -> Result<Vec<TwitchTag>, Box<dyn Error>> {
+    pub async fn get_stream_tags(&self, id: &UserId) -> Result<Vec<TwitchTag>, TwitchCtlError> {
         let tag_req = GetStreamTagsRequest::builder()
             .broadcaster_id(id.clone())
             .build();
-        let tag_res = self.helix_client.req_get(tag_req, &self.token).await?;
+        let tag_res = self.req_get(Operation::GetStreamTags, tag_req).await?;
         Ok(tag_res.data)
     }
 
-    pub async fn get_all_tags(&self) -> Result<Vec<TwitchTag>, Box<dyn Error>> {
+    /// Returns all stream tags.
+    ///
+    /// The tags are only fetched once per client, use [`ApiClient::refresh_tags_cache`]
+    /// to load them again.
+    pub async fn get_all_tags(&self) -> Result<Vec<TwitchTag>, TwitchCtlError> {
+        let mut cache = self.tags_cache.lock().await;
+        if let Some(tags) = &*cache {
+            return Ok(tags.clone());
+        }
+        let tags = self.fetch_all_tags().await?;
+        *cache = Some(tags.clone());
+        Ok(tags)
+    }
+
+    /// Reloads the cached stream tags.
+    pub async fn refresh_tags_cache(&self) -> Result<(), TwitchCtlError> {
+        let mut cache = self.tags_cache.lock().await;
+        *cache = Some(self.fetch_all_tags().await?);
+        Ok(())
+    }
+
+    async fn fetch_all_tags(&self) -> Result<Vec<TwitchTag>, TwitchCtlError> {
         let mut tags = vec![];
         let mut pagination = None;
         loop {
@@ -156,7 +1026,7 @@ impl<'a> ApiClient<'a> {
                 .after(pagination)
                 .first(Some(100))
                 .build();
-            let mut res = self.helix_client.req_get(req, &self.token).await?;
+            let mut res = self.req_get(Operation::GetStreamTags, req).await?;
             tags.append(&mut res.data);
             pagination = res.pagination;
             if pagination == None {
@@ -166,165 +1036,1243 @@ impl<'a> ApiClient<'a> {
         Ok(tags)
     }
 
+    /// Returns the ids of the tags with one of the names in `tags`.
+    ///
+    /// Names without a matching tag are left out, see [`ApiClient::resolve_tag_names`].
     pub async fn get_tag_ids_matching(
         &self,
         tags: &[String],
-        locale: &str,
-    ) -> Result<Vec<TagId>, Box<dyn Error>> {
+        locales: Option<&[&str]>,
+    ) -> Result<Vec<TagId>, TwitchCtlError> {
+        Ok(self.resolve_tag_names(tags, locales).await?.0)
+    }
+
+    /// Returns the ids of the tags with the names in `tags` and the names that did not match
+    /// any tag.
+    ///
+    /// The names are looked up in the first of `locales` a tag has a name for, so the last locale
+    /// is the fallback. Without `locales` the default locale of the client is used, falling back
+    /// to `en-us`.
+    pub async fn resolve_tag_names(
+        &self,
+        tags: &[String],
+        locales: Option<&[&str]>,
+    ) -> Result<(Vec<TagId>, Vec<String>), TwitchCtlError> {
+        let default_locales = [self.default_locale.as_str(), "en-us"];
+        let locales = locales.unwrap_or(&default_locales);
         let all_tags = self.get_all_tags().await?;
 
-        Ok(tags
-            .iter()
-            .filter_map(|tag| {
-                for tag_obj in all_tags.iter() {
-                    match (
-                        tag_obj.localization_names.get(locale),
-                        tag_obj.localization_names.get("en-us"),
-                    ) {
-                        (Some(loc_name), _)
-                            if loc_name.eq_ignore_ascii_case(tag)
-                                && tag_obj.is_auto == AutoGenerated::False =>
-                        {
-                            return Some(tag_obj.id.clone())
-                        }
-                        (None, Some(en_name))
-                            if en_name.eq_ignore_ascii_case(tag)
-                                && tag_obj.is_auto == AutoGenerated::False =>
-                        {
-                            warning!(
-                                "The tag `{}`, has no localized name for `{}`. \
-                                Matched English name instead.",
-                                en_name,
-                                locale
-                            );
-                            return Some(tag_obj.id.clone());
-                        }
-                        _ => {}
-                    }
-                }
-                None
-            })
-            .collect())
+        let mut ids = vec![];
+        let mut unmatched = vec![];
+        for tag in tags {
+            match match_tag(&all_tags, tag, locales) {
+                Some(id) => ids.push(id),
+                None => unmatched.push(tag.clone()),
+            }
+        }
+        Ok((ids, unmatched))
     }
+
     pub async fn get_broadcaster_id(
         &self,
         broadcaster_ident: UserIdent,
-    ) -> Result<UserId, Box<dyn Error>> {
+    ) -> Result<UserId, TwitchCtlError> {
         match broadcaster_ident {
-            UserIdent::None => Ok(self.get_user_id().clone()),
+            UserIdent::None => Ok(self.get_user_id()?.clone()),
             UserIdent::UserId(broadcaster_id) => Ok(broadcaster_id),
             UserIdent::UserName(broadcaster_name) => {
-                match self.get_users(&[&broadcaster_name], &[]).await {
-                    Ok(userlist) => {
-                        if userlist.is_empty() {
-                            Err(Box::new(ApiError::NoUser(broadcaster_name)))
-                        } else {
-                            Ok(userlist[0].id.clone())
-                        }
-                    }
-                    Err(e) => Err(e),
+                if let Some(id) = self.cached_user_id(broadcaster_name.as_str()) {
+                    return Ok(id);
+                }
+                match self.get_user_by_login(&broadcaster_name).await? {
+                    Some(user) => Ok(user.id),
+                    None => Err(TwitchCtlError::NotFound(format!(
+                        "No user with login `{}` found.",
+                        broadcaster_name
+                    ))),
                 }
             }
         }
     }
+
+    /// Resolves multiple broadcasters at once, looking up all names in as few requests as possible.
+    ///
+    /// The ids are returned in the order of `broadcaster_idents`.
+    pub async fn get_broadcaster_ids(
+        &self,
+        broadcaster_idents: Vec<UserIdent>,
+    ) -> Result<Vec<UserId>, TwitchCtlError> {
+        let names: Vec<&Nickname> = broadcaster_idents
+            .iter()
+            .filter_map(|ident| match ident {
+                UserIdent::UserName(name) if self.cached_user_id(name.as_str()).is_none() => {
+                    Some(name)
+                }
+                _ => None,
+            })
+            .collect();
+        // Twitch accepts up to 100 logins per request
+        for names in names.chunks(100) {
+            self.get_users(names, &[]).await?;
+        }
+
+        if broadcaster_idents
+            .iter()
+            .any(|ident| matches!(ident, UserIdent::None))
+        {
+            self.get_user_id()?;
+        }
+        let mut missing = vec![];
+        let ids = broadcaster_idents
+            .iter()
+            .filter_map(|ident| match ident {
+                UserIdent::None => self.user.clone(),
+                UserIdent::UserId(id) => Some(id.clone()),
+                UserIdent::UserName(name) => match self.cached_user_id(name.as_str()) {
+                    Some(id) => Some(id),
+                    None => {
+                        missing.push(format!("`{}`", name));
+                        None
+                    }
+                },
+            })
+            .collect();
+
+        if missing.is_empty() {
+            Ok(ids)
+        } else {
+            Err(TwitchCtlError::NotFound(format!(
+                "No users with login {} found.",
+                missing.join(", ")
+            )))
+        }
+    }
+
+    pub async fn get_channel_information(
+        &self,
+        id: &UserId,
+    ) -> Result<ChannelInfo, TwitchCtlError> {
+        self.cached(format!("channels?broadcaster_id={}", id), || async {
+            let req = GetChannelInformationRequest::builder()
+                .broadcaster_id(id.clone())
+                .build();
+
+            match self
+                .req_get(Operation::GetChannelInformation, req)
+                .await?
+                .data
+            {
+                Some(info) => Ok(info.into()),
+                None => Err(TwitchCtlError::NotFound(format!(
+                    "No channel information found for `{}`.",
+                    id
+                ))),
+            }
+        })
+        .await
+    }
+
+    /// Returns the information of several channels, in the order of `ids`.
+    ///
+    /// Channels that do not exist are left out.
+    pub async fn get_channels_information(
+        &self,
+        ids: &[&UserId],
+    ) -> Result<Vec<ChannelInfo>, TwitchCtlError> {
+        // Twitch accepts up to 100 ids per request
+        let mut channels: Vec<ChannelInfo> = stream::iter(ids.chunks(100))
+            .map(|ids| async move {
+                let query: Vec<_> = ids
+                    .iter()
+                    .map(|id| ("broadcaster_id", id.to_string()))
+                    .collect();
+                let res = self
+                    .helix_data::<Vec<ChannelInformation>, _>(
+                        Operation::GetChannelInformation,
+                        Method::GET,
+                        "channels",
+                        &query,
+                        helix::NO_BODY,
+                    )
+                    .await?;
+                Ok::<_, TwitchCtlError>(
+                    res.data
+                        .into_iter()
+                        .map(ChannelInfo::from)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .buffered(self.max_in_flight)
+            .try_concat()
+            .await?;
+        channels.sort_by_key(|channel| {
+            ids.iter()
+                .position(|id| channel.broadcaster_id.as_ref() == Some(*id))
+        });
+        Ok(channels)
+    }
+
+    /// Changes only the title of a channel.
+    ///
+    /// Fails with [`TwitchCtlError::Validation`] if it is longer than [`MAX_TITLE_LENGTH`].
+    pub async fn set_title(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+    ) -> Result<(), TwitchCtlError> {
+        let info = ChannelInfo {
+            title: Some(title.to_string()),
+            ..Default::default()
+        };
+        self.modify_channel_information(broadcaster_id, info).await
+    }
+
+    /// Sets the category of a channel to the one named `game_name` and returns it.
+    ///
+    /// A category with exactly that name is preferred, otherwise the best search result is
+    /// used with a warning.
+    pub async fn set_category(
+        &self,
+        broadcaster_id: &UserId,
+        game_name: &str,
+    ) -> Result<Category, TwitchCtlError> {
+        let exact = self
+            .search_categories(game_name, 1, CategoryMatch::Exact)
+            .await?
+            .map(|mut categories| categories.remove(0));
+        let category = match exact {
+            Some(category) => category,
+            None => {
+                let category = self.search_category(game_name).await?.ok_or_else(|| {
+                    TwitchCtlError::NotFound(format!(
+                        "Could not find a category for `{}`.",
+                        game_name
+                    ))
+                })?;
+                warning!(
+                    "No category is named `{}`, using `{}`",
+                    game_name,
+                    category.name
+                );
+                category
+            }
+        };
+        let info = ChannelInfo {
+            category: Some(category.id.clone()),
+            ..Default::default()
+        };
+        self.modify_channel_information(broadcaster_id, info)
+            .await?;
+        Ok(category)
+    }
+
+    /// Changes the information of a channel.
+    ///
+    /// Setting a delay fails with [`TwitchCtlError::DelayNotAllowed`] unless the broadcaster
+    /// is a partner.
     pub async fn modify_channel_information(
         &self,
         id: &UserId,
         info: ChannelInfo,
-    ) -> Result<(), Box<dyn Error>> {
-        let req = ModifyChannelInformationRequest::builder()
-            .broadcaster_id(id.clone())
+    ) -> Result<(), TwitchCtlError> {
+        if let Some(title) = &info.title {
+            let length = title.chars().count();
+            if length > MAX_TITLE_LENGTH {
+                return Err(TwitchCtlError::Validation(format!(
+                    "Stream titles can be at most {} characters long, got {}.",
+                    MAX_TITLE_LENGTH, length
+                )));
+            }
+        }
+        if let Some(delay) = info.delay {
+            if delay > 900 {
+                return Err(TwitchCtlError::Validation(
+                    "The stream delay can be at most 900 seconds.".to_string(),
+                ));
+            }
+            let is_partner = self.get_user_by_id(id).await?.map_or(false, |user| {
+                matches!(user.broadcaster_type, Some(BroadcasterType::Partner))
+            });
+            if !is_partner {
+                return Err(TwitchCtlError::DelayNotAllowed);
+            }
+        }
+        // twitch_api2 does not know the content classification labels yet
+        self.helix_send(
+            Operation::ModifyChannelInformation,
+            Method::PATCH,
+            "channels",
+            &[("broadcaster_id", id.to_string())],
+            Some(&info.to_modify_body()),
+        )
+        .await?;
+        self.invalidate_cache(&format!("channels?broadcaster_id={}", id));
+        Ok(())
+    }
+
+    /// Starts a commercial, `length` has to be one of 30, 60, 90, 120, 150 or 180 seconds.
+    ///
+    /// Returns `None` in a dry run.
+    pub async fn start_commercial(
+        &self,
+        broadcaster_id: &UserId,
+        length: u32,
+    ) -> Result<Option<Commercial>, TwitchCtlError> {
+        let length = match length {
+            30 => CommercialLength::Length30,
+            60 => CommercialLength::Length60,
+            90 => CommercialLength::Length90,
+            120 => CommercialLength::Length120,
+            150 => CommercialLength::Length150,
+            180 => CommercialLength::Length180,
+            _ => {
+                return Err(TwitchCtlError::Validation(format!(
+                    "Invalid commercial length `{}`, valid lengths are 30, 60, 90, 120, 150 and 180 seconds.",
+                    length
+                )))
+            }
+        };
+        let req = StartCommercialRequest::builder().build();
+        let body = StartCommercialBody::builder()
+            .broadcaster_id(broadcaster_id.clone())
+            .length(length)
             .build();
 
-        let body = info.to_modify_body();
-        self.helix_client.req_patch(req, body, &self.token).await?;
-        Ok(())
+        let res = match self.req_post(Operation::StartCommercial, req, body).await? {
+            Some(res) => res,
+            None => return Ok(None),
+        };
+        match res.data.into_iter().next() {
+            Some(commercial) => Ok(Some(Commercial {
+                length: commercial.length,
+                retry_after: commercial.retry_after,
+            })),
+            None => Err(TwitchCtlError::Helix(
+                "Twitch did not confirm the commercial".into(),
+            )),
+        }
     }
 
     pub async fn create_custom_reward(
         &self,
         id: &UserId,
         reward: CreateCustomRewardBody,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), TwitchCtlError> {
+        check_prompt(
+            &reward.title,
+            reward.is_user_input_required,
+            reward.prompt.as_deref(),
+        )?;
         let req = CreateCustomRewardRequest::builder()
             .broadcaster_id(id.clone())
             .build();
 
-        self.helix_client.req_post(req, reward, &self.token).await?;
+        self.req_post(Operation::CreateRewards, req, reward).await?;
         Ok(())
     }
 
-    pub async fn update_custom_reward(
+    /// Creates all `rewards`, continuing with the next reward when one fails.
+    ///
+    /// Returns the index of every reward in `rewards` with the created reward or the error. The
+    /// created rewards are `None` in a dry run.
+    pub async fn create_custom_rewards(
         &self,
         broadcaster_id: &UserId,
-        reward_id: &RewardId,
-        reward: UpdateCustomRewardBody,
-    ) -> Result<(), Box<dyn Error>> {
+        rewards: Vec<CreateCustomRewardBody>,
+    ) -> Result<Vec<(usize, Result<Option<CustomReward>, TwitchCtlError>)>, TwitchCtlError> {
+        self.get_user_id()?;
+        let query = &[("broadcaster_id", broadcaster_id.to_string())];
+        let results = stream::iter(rewards.iter().enumerate())
+            .map(|(i, reward)| async move {
+                let prompt = reward.prompt.as_deref();
+                if let Err(e) = check_prompt(&reward.title, reward.is_user_input_required, prompt) {
+                    return (i, Err(e));
+                }
+                let res = self
+                    .helix_write::<Vec<CustomReward>, _>(
+                        Operation::CreateRewards,
+                        Method::POST,
+                        "channel_points/custom_rewards",
+                        query,
+                        Some(reward),
+                    )
+                    .await
+                    .and_then(|res| {
+                        res.map(|res| {
+                            res.data.into_iter().next().ok_or_else(|| {
+                                TwitchCtlError::Helix(
+                                    "Twitch did not return the created reward".into(),
+                                )
+                            })
+                        })
+                        .transpose()
+                    });
+                (i, res)
+            })
+            .buffered(self.max_in_flight)
+            .collect()
+            .await;
+        Ok(results)
+    }
+
+    pub async fn update_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        reward: UpdateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError> {
         let req = UpdateCustomRewardRequest::builder()
             .broadcaster_id(broadcaster_id.clone())
             .id(reward_id.clone())
             .build();
-        self.helix_client
-            .req_patch(req, reward, &self.token)
+        self.req_patch(Operation::ManageRewards, req, reward)
             .await?;
         Ok(())
     }
 
-    pub async fn get_rewards(&self, id: &UserId) -> Result<Vec<CustomReward>, Box<dyn Error>> {
-        let tag_req = GetCustomRewardRequest::builder()
+    /// Enables or disables a custom reward, leaving all other settings untouched.
+    pub async fn set_reward_enabled(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        enabled: bool,
+    ) -> Result<(), TwitchCtlError> {
+        self.update_custom_reward(
+            broadcaster_id,
+            reward_id,
+            UpdateCustomRewardBody::builder()
+                .is_enabled(enabled)
+                .build(),
+        )
+        .await
+    }
+
+    /// Pauses or resumes a custom reward, leaving all other settings untouched.
+    pub async fn set_reward_paused(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        paused: bool,
+    ) -> Result<(), TwitchCtlError> {
+        self.update_custom_reward(
+            broadcaster_id,
+            reward_id,
+            UpdateCustomRewardBody::builder().is_paused(paused).build(),
+        )
+        .await
+    }
+
+    /// Deletes a custom reward.
+    ///
+    /// A reward that does not exist (anymore) is not treated as an error.
+    pub async fn delete_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+    ) -> Result<(), TwitchCtlError> {
+        self.confirmed(&format!("Delete the reward {}?", reward_id))?;
+        let req = DeleteCustomRewardRequest::builder()
+            .broadcaster_id(broadcaster_id.clone())
+            .id(reward_id.clone())
+            .build();
+        match self.req_delete(Operation::ManageRewards, req).await {
+            Ok(_) | Err(TwitchCtlError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Streams the custom rewards of a broadcaster.
+    ///
+    /// Twitch returns all custom rewards (at most 50) in a single response without a
+    /// pagination cursor, so this issues one request and yields the rewards from it.
+    pub fn rewards_stream<'s>(
+        &'s self,
+        id: &'s UserId,
+    ) -> impl Stream<Item = Result<CustomReward, TwitchCtlError>> + 's {
+        stream::once(async move {
+            let req = GetCustomRewardRequest::builder()
+                .broadcaster_id(id.clone())
+                .build();
+            let res = self.req_get(Operation::GetRewards, req).await?;
+            Ok::<_, TwitchCtlError>(stream::iter(res.data.into_iter().map(Ok)))
+        })
+        .try_flatten()
+    }
+
+    pub async fn get_rewards(&self, id: &UserId) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        self.rewards_stream(id).try_collect().await
+    }
+
+    /// Returns the rewards of a broadcaster matching `filter`, e.g. the paused ones.
+    pub async fn get_rewards_filtered(
+        &self,
+        id: &UserId,
+        filter: RewardFilter,
+    ) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        let mut rewards = self.get_rewards(id).await?;
+        rewards.retain(|reward| filter.matches(reward));
+        Ok(rewards)
+    }
+
+    /// Returns only the rewards created with the client id of the token,
+    /// Twitch does not allow changing the other ones.
+    pub async fn get_manageable_rewards(
+        &self,
+        id: &UserId,
+    ) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        let req = GetCustomRewardRequest::builder()
             .broadcaster_id(id.clone())
+            .only_manageable_rewards(Some(true))
             .build();
-        let tag_res = self.helix_client.req_get(tag_req, &self.token).await?;
-        Ok(tag_res.data)
+        Ok(self.req_get(Operation::GetRewards, req).await?.data)
+    }
+
+    /// Returns the reward with the id `reward_id`, or `None` if the broadcaster has no such reward.
+    pub async fn get_reward_by_id(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+    ) -> Result<Option<CustomReward>, TwitchCtlError> {
+        let req = GetCustomRewardRequest::builder()
+            .broadcaster_id(broadcaster_id.clone())
+            .id(Some(reward_id.clone()))
+            .build();
+        match self.req_get(Operation::GetRewards, req).await {
+            Ok(res) => Ok(res.data.into_iter().next()),
+            Err(TwitchCtlError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns all redemptions of a reward with the given status.
+    pub async fn get_redemptions(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<Vec<CustomRewardRedemption>, TwitchCtlError> {
+        let mut redemptions = vec![];
+        let mut pagination = None;
+        loop {
+            let req = GetCustomRewardRedemptionRequest::builder()
+                .broadcaster_id(broadcaster_id.clone())
+                .reward_id(reward_id.clone())
+                .status(status.clone())
+                .after(pagination)
+                .first(Some(50))
+                .build();
+            let mut res = self.req_get(Operation::GetRedemptions, req).await?;
+            redemptions.append(&mut res.data);
+            pagination = res.pagination;
+            if pagination == None {
+                break;
+            }
+        }
+        Ok(redemptions)
+    }
+
+    /// Returns a page of the redemptions of a reward with the given status.
+    ///
+    /// Twitch returns at most 50 redemptions per page.
+    pub async fn get_redemptions_page(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        status: CustomRewardRedemptionStatus,
+        page: &Page,
+    ) -> Result<Paged<CustomRewardRedemption>, TwitchCtlError> {
+        let req = GetCustomRewardRedemptionRequest::builder()
+            .broadcaster_id(broadcaster_id.clone())
+            .reward_id(reward_id.clone())
+            .status(status)
+            .after(page.after.clone())
+            .first(Some(page.first.unwrap_or(50).min(50)))
+            .build();
+        let res = self.req_get(Operation::GetRedemptions, req).await?;
+        Ok(Paged {
+            data: res.data,
+            cursor: res.pagination,
+        })
     }
 
+    /// Sets the status of redemptions, e.g. to fulfill or refund them.
+    ///
+    /// Twitch accepts up to 50 redemptions per request, more are split into multiple requests.
+    pub async fn update_redemption_status(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        redemption_ids: &[RedemptionId],
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<(), TwitchCtlError> {
+        for ids in redemption_ids.chunks(50) {
+            let req = UpdateRedemptionStatusRequest::builder()
+                .broadcaster_id(broadcaster_id.clone())
+                .reward_id(reward_id.clone())
+                .id(ids.to_vec())
+                .build();
+            let body = UpdateRedemptionStatusBody::builder()
+                .status(status.clone())
+                .build();
+            self.req_patch(Operation::UpdateRedemptionStatus, req, body)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the reward matching `query`, `None` if no or several rewards match.
+    ///
+    /// See [`ApiClient::lookup_reward`] for how rewards are matched.
     pub async fn find_reward(
         &self,
         id: &UserId,
         query: &str,
-    ) -> Result<Option<CustomReward>, Box<dyn Error>> {
-        let rewards = self.get_rewards(id).await?;
+    ) -> Result<Option<CustomReward>, TwitchCtlError> {
+        match self.lookup_reward(id, query).await? {
+            RewardLookup::Found(reward) => Ok(Some(reward)),
+            RewardLookup::NotFound | RewardLookup::Ambiguous(_) => Ok(None),
+        }
+    }
 
-        if let Some(reward) = rewards.iter().find(|r| r.title == query) {
-            Ok(Some(reward.clone()))
-        } else {
-            let query = query.to_lowercase();
-            let rewards_ic: Vec<_> = rewards
-                .iter()
-                .filter(|r| r.title.to_lowercase() == query)
-                .collect();
-            if rewards_ic.len() == 1 {
-                Ok(Some((rewards_ic[0]).clone()))
-            } else {
-                let query = FuzzyFilter::new(&query);
-                let mut rewards = rewards
-                    .iter()
-                    .filter(|CustomReward { title, .. }| query.matches(&title.to_lowercase()));
-                let reward = rewards.next();
+    /// Looks up the reward matching `query`.
+    ///
+    /// A reward with exactly the title `query` is preferred, then the rewards with the title
+    /// ignoring case and diacritics, then the rewards matching fuzzily.
+    pub async fn lookup_reward(
+        &self,
+        id: &UserId,
+        query: &str,
+    ) -> Result<RewardLookup, TwitchCtlError> {
+        Ok(lookup_in_rewards(self.get_rewards(id).await?, query))
+    }
 
-                if reward.is_some() && !rewards.next().is_some() {
-                    Ok(Some(reward.unwrap().clone()))
-                } else {
-                    Ok(None)
+    /// Changes the fields set in `patch` of the reward matching `title`.
+    ///
+    /// Rewards are matched like in [`ApiClient::lookup_reward`]. Fails if no or several rewards
+    /// match, so the wrong reward is never changed.
+    pub async fn patch_reward_by_title(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        patch: RewardPatch,
+    ) -> Result<(), TwitchCtlError> {
+        let reward = match self.lookup_reward(broadcaster_id, title).await? {
+            RewardLookup::Found(reward) => reward,
+            RewardLookup::NotFound => {
+                return Err(TwitchCtlError::NotFound(format!(
+                    "Did not find a reward matching `{}`.",
+                    title
+                )))
+            }
+            RewardLookup::Ambiguous(rewards) => {
+                return Err(TwitchCtlError::AmbiguousReward(
+                    rewards.into_iter().map(|reward| reward.title).collect(),
+                ))
+            }
+        };
+        self.update_custom_reward(broadcaster_id, &reward.id, patch.into())
+            .await
+    }
+
+    /// Returns every reward whose title fuzzy matches `query` with its score, the best matches
+    /// first.
+    ///
+    /// The score is 0 for an exact match and lower the worse the title matches, e.g. to only
+    /// suggest rewards close to what was typed.
+    pub async fn search_rewards(
+        &self,
+        id: &UserId,
+        query: &str,
+    ) -> Result<Vec<(CustomReward, i64)>, TwitchCtlError> {
+        Ok(score_rewards(self.get_rewards(id).await?, query))
+    }
+
+    /// Returns every reward whose title fuzzy matches `query`, the closest matches first.
+    ///
+    /// Matches are ranked by how close together the characters of `query` appear in the title,
+    /// then by the length of the title.
+    pub async fn find_rewards(
+        &self,
+        id: &UserId,
+        query: &str,
+    ) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        Ok(rank_rewards(self.get_rewards(id).await?, query))
+    }
+}
+
+/// Fails if the reward requires user input without telling the viewers what to enter,
+/// warns if it has a prompt for input it does not ask for.
+fn check_prompt(
+    title: &str,
+    is_user_input_required: Option<bool>,
+    prompt: Option<&str>,
+) -> Result<(), TwitchCtlError> {
+    let has_prompt = prompt.map_or(false, |prompt| !prompt.trim().is_empty());
+    match is_user_input_required {
+        Some(true) if !has_prompt => Err(TwitchCtlError::Validation(PROMPT_REQUIRED.to_string())),
+        Some(false) | None if has_prompt => {
+            warning!(
+                "The reward `{}` has a prompt but does not require user input.",
+                title
+            );
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+const PROMPT_REQUIRED: &str = "prompt required when user input is required";
+
+fn lookup_in_rewards(rewards: Vec<CustomReward>, query: &str) -> RewardLookup {
+    if let Some(reward) = rewards.iter().find(|r| r.title == query) {
+        return RewardLookup::Found(reward.clone());
+    }
+    let normalized = normalize_title(query);
+    let rewards_ic: Vec<_> = rewards
+        .iter()
+        .filter(|r| normalize_title(&r.title) == normalized)
+        .cloned()
+        .collect();
+    let mut candidates = if rewards_ic.is_empty() {
+        rank_rewards(rewards, query)
+    } else {
+        rewards_ic
+    };
+    match candidates.len() {
+        0 => RewardLookup::NotFound,
+        1 => RewardLookup::Found(candidates.remove(0)),
+        _ => RewardLookup::Ambiguous(candidates),
+    }
+}
+
+fn rank_rewards(rewards: Vec<CustomReward>, query: &str) -> Vec<CustomReward> {
+    score_rewards(rewards, query)
+        .into_iter()
+        .map(|(reward, _)| reward)
+        .collect()
+}
+
+/// Returns the rewards matching `query` with their score, the best first.
+///
+/// An exact match scores 0, every character between the matched ones costs 100 and every other
+/// character of the title costs 1. A query of only whitespace matches nothing.
+fn score_rewards(rewards: Vec<CustomReward>, query: &str) -> Vec<(CustomReward, i64)> {
+    let query = normalize_title(query);
+    let query_len = query.chars().filter(|c| !c.is_whitespace()).count();
+    if query_len == 0 {
+        return vec![];
+    }
+    let filter = FuzzyFilter::new(&query);
+    let mut rewards: Vec<_> = rewards
+        .into_iter()
+        .filter_map(|reward| {
+            let title = normalize_title(&reward.title);
+            if !filter.matches(&title) {
+                return None;
+            }
+            let span = match_span(&query, &title)?;
+            let gaps = span.saturating_sub(query_len) as i64;
+            let rest = title.chars().count().saturating_sub(span) as i64;
+            Some((reward, -(gaps.saturating_mul(100).saturating_add(rest))))
+        })
+        .collect();
+    rewards.sort_by_key(|(_, score)| -score);
+    rewards
+}
+
+/// Returns the id of the tag named `tag`, which can not be an automatic tag
+fn match_tag(all_tags: &[TwitchTag], tag: &str, locales: &[&str]) -> Option<TagId> {
+    for tag_obj in all_tags.iter() {
+        if tag_obj.is_auto != AutoGenerated::False {
+            continue;
+        }
+        let localized = locales.iter().enumerate().find_map(|(i, locale)| {
+            tag_obj
+                .localization_names
+                .get(*locale)
+                .map(|name| (i, locale, name))
+        });
+        match localized {
+            Some((i, locale, name)) if name.eq_ignore_ascii_case(tag) => {
+                if i > 0 {
+                    warning!(
+                        "The tag `{}`, has no localized name for `{}`. \
+                        Matched name for `{}` instead.",
+                        name,
+                        locales[0],
+                        locale
+                    );
                 }
+                return Some(tag_obj.id.clone());
             }
+            _ => {}
         }
     }
+    None
+}
+
+/// The key of a login in the cache of user ids, logins are case insensitive
+fn login_key(login: &str) -> Nickname {
+    Nickname::from(login.to_lowercase())
+}
+
+/// Lowercases `title` and strips diacritics, so `Café` matches `cafe`
+fn normalize_title(title: &str) -> String {
+    title
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
 }
 
-#[derive(Default, Builder, Debug)]
+/// The number of characters to insert, delete or replace to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let replace = previous[j] + if a == *b { 0 } else { 1 };
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The number of characters from the first to the last character of `query` found in `title`,
+/// matching each character as early as possible, or `None` if `title` does not contain every
+/// character of `query` in order or `query` is only whitespace
+fn match_span(query: &str, title: &str) -> Option<usize> {
+    let mut query = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut start = None;
+    let mut end = 0;
+    for (i, c) in title.chars().enumerate() {
+        match query.peek() {
+            Some(&q) if q == c => {
+                start.get_or_insert(i);
+                end = i;
+                query.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+    match query.peek() {
+        Some(_) => None,
+        None => start.map(|start| end - start + 1),
+    }
+}
+
+/// A commercial that was started
+#[derive(Debug, Clone)]
+pub struct Commercial {
+    /// the length of the commercial in seconds
+    pub length: u64,
+    /// the seconds until the next commercial can be started
+    pub retry_after: u64,
+}
+
+#[derive(Default, Builder, Debug, Clone)]
 #[builder(public, setter(into), default)]
 pub struct ChannelInfo {
+    /// only set when read from Twitch
+    #[builder(setter(skip))]
+    broadcaster_id: Option<UserId>,
     title: Option<String>,
     language: Option<String>,
     category: Option<CategoryId>,
+    /// the name of `category`, only set when read from Twitch
+    #[builder(setter(skip))]
+    game_name: Option<String>,
+    /// the labels to add to the channel, or to remove if `false`
+    content_labels: Vec<(ContentLabel, bool)>,
+    /// whether the stream is sponsored
+    is_branded_content: Option<bool>,
+    /// the stream delay in seconds, only partners can set it
+    delay: Option<u32>,
+}
+
+impl ChannelInfoBuilder {
+    /// Adds `label` to the channel, or removes it if `enabled` is `false`.
+    pub fn content_label(&mut self, label: ContentLabel, enabled: bool) -> &mut Self {
+        self.content_labels
+            .get_or_insert_with(Vec::new)
+            .push((label, enabled));
+        self
+    }
+}
+
+impl From<ChannelInformation> for ChannelInfo {
+    fn from(info: ChannelInformation) -> Self {
+        ChannelInfo {
+            broadcaster_id: Some(info.broadcaster_id),
+            title: Some(info.title),
+            language: Some(info.broadcaster_language),
+            category: Some(info.game_id),
+            game_name: Some(info.game_name),
+            ..Default::default()
+        }
+    }
 }
 impl ChannelInfo {
-    fn to_modify_body(&self) -> ModifyChannelInformationBody {
-        ModifyChannelInformationBody::builder()
-            .broadcaster_language(self.language.clone())
-            .game_id(self.category.clone())
-            .title(self.title.clone())
+    pub fn broadcaster_id(&self) -> Option<&UserId> {
+        self.broadcaster_id.as_ref()
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub fn category(&self) -> Option<&CategoryId> {
+        self.category.as_ref()
+    }
+
+    pub fn game_name(&self) -> Option<&str> {
+        self.game_name.as_deref()
+    }
+
+    pub fn delay(&self) -> Option<u32> {
+        self.delay
+    }
+
+    fn to_modify_body(&self) -> ModifyChannelBody<'_> {
+        ModifyChannelBody {
+            game_id: self.category.as_ref(),
+            broadcaster_language: self.language.as_deref(),
+            title: self.title.as_deref(),
+            is_branded_content: self.is_branded_content,
+            delay: self.delay,
+            content_classification_labels: self
+                .content_labels
+                .iter()
+                .map(|(label, is_enabled)| ContentLabelBody {
+                    id: label.as_str(),
+                    is_enabled: *is_enabled,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A content classification label a broadcaster can set on their channel
+///
+/// `MatureGame` is missing, Twitch sets it from the category.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContentLabel {
+    DebatedSocialIssuesAndPolitics,
+    DrugsIntoxication,
+    SexualThemes,
+    ViolentGraphic,
+    Gambling,
+    ProfanityVulgarity,
+}
+
+impl ContentLabel {
+    /// The id of the label in Helix
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentLabel::DebatedSocialIssuesAndPolitics => "DebatedSocialIssuesAndPolitics",
+            ContentLabel::DrugsIntoxication => "DrugsIntoxication",
+            ContentLabel::SexualThemes => "SexualThemes",
+            ContentLabel::ViolentGraphic => "ViolentGraphic",
+            ContentLabel::Gambling => "Gambling",
+            ContentLabel::ProfanityVulgarity => "ProfanityVulgarity",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ModifyChannelBody<'s> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    game_id: Option<&'s CategoryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broadcaster_language: Option<&'s str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'s str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_branded_content: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delay: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    content_classification_labels: Vec<ContentLabelBody>,
+}
+
+#[derive(Serialize)]
+struct ContentLabelBody {
+    id: &'static str,
+    is_enabled: bool,
+}
+
+/// A reward to create, checked for settings Twitch would reject when built
+#[derive(Builder, Debug, Clone)]
+#[builder(public, setter(into), build_fn(validate = "Self::validate"))]
+pub struct RewardSpec {
+    title: String,
+    cost: usize,
+    #[builder(setter(strip_option), default)]
+    prompt: Option<String>,
+    #[builder(default = "true")]
+    is_enabled: bool,
+    /// hex color with # prefix, e.g. `#00E5CB`
+    #[builder(setter(strip_option), default)]
+    background_color: Option<String>,
+    #[builder(default)]
+    is_user_input_required: bool,
+    #[builder(default)]
+    is_max_per_stream_enabled: bool,
+    #[builder(setter(strip_option), default)]
+    max_per_stream: Option<usize>,
+    #[builder(default)]
+    is_max_per_user_per_stream_enabled: bool,
+    #[builder(setter(strip_option), default)]
+    max_per_user_per_stream: Option<usize>,
+    #[builder(default)]
+    is_global_cooldown_enabled: bool,
+    /// the cooldown in seconds
+    #[builder(setter(strip_option), default)]
+    global_cooldown_seconds: Option<usize>,
+    /// redemptions are fulfilled immediately instead of waiting in the request queue
+    #[builder(default)]
+    should_redemptions_skip_request_queue: bool,
+}
+
+impl RewardSpecBuilder {
+    /// Sets the limit fields from `limits`, limits left as `None` are disabled.
+    pub fn limits(&mut self, limits: RewardLimits) -> &mut Self {
+        let (enabled, max) = limit_fields(limits.max_per_stream);
+        self.is_max_per_stream_enabled = Some(enabled.unwrap_or(false));
+        self.max_per_stream = Some(max);
+        let (enabled, max) = limit_fields(limits.max_per_user_per_stream);
+        self.is_max_per_user_per_stream_enabled = Some(enabled.unwrap_or(false));
+        self.max_per_user_per_stream = Some(max);
+        let (enabled, seconds) = limit_fields(limits.global_cooldown_seconds);
+        self.is_global_cooldown_enabled = Some(enabled.unwrap_or(false));
+        self.global_cooldown_seconds = Some(seconds);
+        self
+    }
+
+    /// Fulfills redemptions immediately, see `should_redemptions_skip_request_queue`.
+    pub fn skip_request_queue(&mut self, skip_request_queue: bool) -> &mut Self {
+        self.should_redemptions_skip_request_queue = Some(skip_request_queue);
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        check_prompt(
+            self.title.as_deref().unwrap_or_default(),
+            self.is_user_input_required,
+            self.prompt.as_ref().and_then(Option::as_deref),
+        )
+        .map_err(|e| e.to_string())?;
+        if self.should_redemptions_skip_request_queue == Some(true)
+            && self.is_user_input_required == Some(true)
+        {
+            // the input is never looked at when the redemption does not wait in the queue
+            warning!(
+                "The redemptions of `{}` skip the request queue but require user input.",
+                self.title.as_deref().unwrap_or_default()
+            );
+        }
+        if let Some(Some(color)) = &self.background_color {
+            let is_hex = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex {
+                return Err(format!(
+                    "The background color `{}` is not of the format #RRGGBB.",
+                    color
+                ));
+            }
+        }
+        let limits = [
+            (
+                "max per stream",
+                self.is_max_per_stream_enabled,
+                self.max_per_stream,
+            ),
+            (
+                "max per user per stream",
+                self.is_max_per_user_per_stream_enabled,
+                self.max_per_user_per_stream,
+            ),
+            (
+                "global cooldown",
+                self.is_global_cooldown_enabled,
+                self.global_cooldown_seconds,
+            ),
+        ];
+        for (name, enabled, value) in limits.iter() {
+            if enabled.unwrap_or(false) && value.flatten().unwrap_or(0) == 0 {
+                return Err(format!(
+                    "The {} is enabled, so it has to be set to a positive value.",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<RewardSpec> for CreateCustomRewardBody {
+    fn from(spec: RewardSpec) -> Self {
+        CreateCustomRewardBody::builder()
+            .title(spec.title)
+            .cost(spec.cost)
+            .prompt(spec.prompt)
+            .is_enabled(spec.is_enabled)
+            .background_color(spec.background_color)
+            .is_user_input_required(spec.is_user_input_required)
+            .is_max_per_stream_enabled(spec.is_max_per_stream_enabled)
+            .max_per_stream(spec.max_per_stream)
+            .is_max_per_user_per_stream_enabled(spec.is_max_per_user_per_stream_enabled)
+            .max_per_user_per_stream(spec.max_per_user_per_stream)
+            .is_global_cooldown_enabled(spec.is_global_cooldown_enabled)
+            .global_cooldown_seconds(spec.global_cooldown_seconds)
+            .should_redemptions_skip_request_queue(spec.should_redemptions_skip_request_queue)
+            .build()
+    }
+}
+
+/// Which rewards [`ApiClient::get_rewards_filtered`] returns, `None` does not filter
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RewardFilter {
+    pub enabled: Option<bool>,
+    pub paused: Option<bool>,
+    /// whether the global cooldown of the reward is running
+    pub in_cooldown: Option<bool>,
+}
+
+impl RewardFilter {
+    pub fn matches(&self, reward: &CustomReward) -> bool {
+        self.enabled
+            .map_or(true, |enabled| reward.is_enabled == enabled)
+            && self
+                .paused
+                .map_or(true, |paused| reward.is_paused == paused)
+            && self.in_cooldown.map_or(true, |in_cooldown| {
+                reward.cooldown_expires_at.is_some() == in_cooldown
+            })
+    }
+}
+
+/// How often a reward can be redeemed.
+///
+/// A limit of `0` disables it and `None` leaves it as it is, so a limit can not be enabled
+/// without a value.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RewardLimits {
+    pub max_per_stream: Option<usize>,
+    pub max_per_user_per_stream: Option<usize>,
+    /// the cooldown in seconds
+    pub global_cooldown_seconds: Option<usize>,
+}
+
+impl RewardLimits {
+    /// Disables all limits
+    pub fn disabled() -> Self {
+        RewardLimits {
+            max_per_stream: Some(0),
+            max_per_user_per_stream: Some(0),
+            global_cooldown_seconds: Some(0),
+        }
+    }
+
+    pub fn per_stream(max: usize) -> Self {
+        Self::default().with_per_stream(max)
+    }
+
+    pub fn per_user_per_stream(max: usize) -> Self {
+        Self::default().with_per_user_per_stream(max)
+    }
+
+    pub fn global_cooldown(seconds: usize) -> Self {
+        Self::default().with_global_cooldown(seconds)
+    }
+
+    pub fn with_per_stream(mut self, max: usize) -> Self {
+        self.max_per_stream = Some(max);
+        self
+    }
+
+    pub fn with_per_user_per_stream(mut self, max: usize) -> Self {
+        self.max_per_user_per_stream = Some(max);
+        self
+    }
+
+    pub fn with_global_cooldown(mut self, seconds: usize) -> Self {
+        self.global_cooldown_seconds = Some(seconds);
+        self
+    }
+}
+
+/// Whether a limit is enabled and its value, `None` if it is left as it is
+fn limit_fields(limit: Option<usize>) -> (Option<bool>, Option<usize>) {
+    match limit {
+        None => (None, None),
+        Some(0) => (Some(false), None),
+        Some(value) => (Some(true), Some(value)),
+    }
+}
+
+/// Changes to a reward, only the fields that are set are changed
+#[derive(Debug, Clone, Default)]
+pub struct RewardPatch {
+    pub title: Option<String>,
+    pub cost: Option<usize>,
+    pub prompt: Option<String>,
+    pub is_enabled: Option<bool>,
+    /// hex color with # prefix, e.g. `#00E5CB`
+    pub background_color: Option<String>,
+    pub is_user_input_required: Option<bool>,
+    pub is_max_per_stream_enabled: Option<bool>,
+    pub max_per_stream: Option<usize>,
+    pub is_max_per_user_per_stream_enabled: Option<bool>,
+    pub max_per_user_per_stream: Option<usize>,
+    pub is_global_cooldown_enabled: Option<bool>,
+    /// the cooldown in seconds
+    pub global_cooldown_seconds: Option<usize>,
+    pub is_paused: Option<bool>,
+    pub should_redemptions_skip_request_queue: Option<bool>,
+}
+
+impl RewardPatch {
+    /// Sets the limit fields from `limits`.
+    pub fn with_limits(mut self, limits: RewardLimits) -> Self {
+        let (enabled, max) = limit_fields(limits.max_per_stream);
+        self.is_max_per_stream_enabled = enabled;
+        self.max_per_stream = max;
+        let (enabled, max) = limit_fields(limits.max_per_user_per_stream);
+        self.is_max_per_user_per_stream_enabled = enabled;
+        self.max_per_user_per_stream = max;
+        let (enabled, seconds) = limit_fields(limits.global_cooldown_seconds);
+        self.is_global_cooldown_enabled = enabled;
+        self.global_cooldown_seconds = seconds;
+        self
+    }
+}
+
+impl From<RewardPatch> for UpdateCustomRewardBody {
+    fn from(patch: RewardPatch) -> Self {
+        UpdateCustomRewardBody::builder()
+            .title(patch.title)
+            .cost(patch.cost)
+            .prompt(patch.prompt)
+            .is_enabled(patch.is_enabled)
+            .background_color(patch.background_color)
+            .is_user_input_required(patch.is_user_input_required)
+            .is_max_per_stream_enabled(patch.is_max_per_stream_enabled)
+            .max_per_stream(patch.max_per_stream)
+            .is_max_per_user_per_stream_enabled(patch.is_max_per_user_per_stream_enabled)
+            .max_per_user_per_stream(patch.max_per_user_per_stream)
+            .is_global_cooldown_enabled(patch.is_global_cooldown_enabled)
+            .global_cooldown_seconds(patch.global_cooldown_seconds)
+            .is_paused(patch.is_paused)
+            .should_redemptions_skip_request_queue(patch.should_redemptions_skip_request_queue)
             .build()
     }
 }
@@ -346,3 +2294,202 @@ pub async fn get_broadcaster_id_or_die(
         Err(e) => exit!(1, "{}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reward(title: &str) -> CustomReward {
+        serde_json::from_value(serde_json::json!({
+            "broadcaster_id": "274637212",
+            "broadcaster_login": "torpedo09",
+            "broadcaster_name": "torpedo09",
+            "id": format!("id-{}", title),
+            "image": null,
+            "background_color": "#00E5CB",
+            "is_enabled": true,
+            "cost": 50000,
+            "title": title,
+            "prompt": "",
+            "is_user_input_required": false,
+            "max_per_stream_setting": {"is_enabled": false, "max_per_stream": 0},
+            "max_per_user_per_stream_setting": {
+                "is_enabled": false,
+                "max_per_user_per_stream": 0
+            },
+            "global_cooldown_setting": {"is_enabled": false, "global_cooldown_seconds": 0},
+            "is_paused": false,
+            "is_in_stock": true,
+            "default_image": {
+                "url_1x": "https://static-cdn.jtvnw.net/custom-reward-images/default-1.png",
+                "url_2x": "https://static-cdn.jtvnw.net/custom-reward-images/default-2.png",
+                "url_4x": "https://static-cdn.jtvnw.net/custom-reward-images/default-4.png"
+            },
+            "should_redemptions_skip_request_queue": false,
+            "redemptions_redeemed_current_stream": null,
+            "cooldown_expires_at": null
+        }))
+        .expect("the reward is valid")
+    }
+
+    fn found_title(lookup: RewardLookup) -> Option<String> {
+        match lookup {
+            RewardLookup::Found(reward) => Some(reward.title),
+            RewardLookup::NotFound | RewardLookup::Ambiguous(_) => None,
+        }
+    }
+
+    #[test]
+    fn accents_are_ignored() {
+        assert_eq!(normalize_title("Café"), normalize_title("cafe"));
+        let rewards = vec![reward("Café au lait"), reward("Hydrate")];
+        assert_eq!(
+            found_title(lookup_in_rewards(rewards.clone(), "cafe au lait")),
+            Some("Café au lait".to_string())
+        );
+        assert_eq!(
+            found_title(lookup_in_rewards(rewards, "CAFÉ")),
+            Some("Café au lait".to_string())
+        );
+    }
+
+    #[test]
+    fn emoji_titles_do_not_panic() {
+        let rewards = vec![
+            reward("💧 Hydrate 💧"),
+            reward("🎵 Song request"),
+            reward("Ñandú 🐦"),
+        ];
+        assert_eq!(
+            found_title(lookup_in_rewards(rewards.clone(), "hydrate")),
+            Some("💧 Hydrate 💧".to_string())
+        );
+        assert_eq!(
+            found_title(lookup_in_rewards(rewards.clone(), "🎵")),
+            Some("🎵 Song request".to_string())
+        );
+        assert_eq!(
+            found_title(lookup_in_rewards(rewards.clone(), "nandu")),
+            Some("Ñandú 🐦".to_string())
+        );
+        for query in &["💧", "🐦💧", "é", "x"] {
+            lookup_in_rewards(rewards.clone(), query);
+        }
+    }
+
+    #[test]
+    fn blank_queries_match_nothing() {
+        let rewards = vec![reward("Hydrate"), reward("Song request")];
+        for query in &["", "   ", "\t\n"] {
+            assert!(score_rewards(rewards.clone(), query).is_empty());
+        }
+        assert_eq!(match_span("", "hydrate"), None);
+        assert_eq!(match_span("hdr", "hydrate"), Some(4));
+        assert_eq!(match_span("hx", "hydrate"), None);
+    }
+
+    #[test]
+    fn user_input_requires_a_prompt() {
+        let err = check_prompt("Song request", Some(true), None).unwrap_err();
+        assert_eq!(err.to_string(), PROMPT_REQUIRED);
+        assert!(check_prompt("Song request", Some(true), Some("  ")).is_err());
+        assert!(check_prompt("Song request", Some(true), Some("Which song?")).is_ok());
+
+        let err = RewardSpecBuilder::default()
+            .title("Song request")
+            .cost(100usize)
+            .is_user_input_required(true)
+            .build()
+            .unwrap_err();
+        assert_eq!(err.to_string(), PROMPT_REQUIRED);
+    }
+
+    #[test]
+    fn prompt_without_user_input_only_warns() {
+        assert!(check_prompt("Hydrate", Some(false), Some("What to drink?")).is_ok());
+        assert!(check_prompt("Hydrate", None, Some("What to drink?")).is_ok());
+
+        let spec = RewardSpecBuilder::default()
+            .title("Hydrate")
+            .cost(100usize)
+            .prompt("What to drink?")
+            .build();
+        assert!(spec.is_ok());
+    }
+
+    #[test]
+    fn skipping_the_request_queue_is_sent() {
+        let spec = RewardSpecBuilder::default()
+            .title("Hydrate")
+            .cost(100usize)
+            .skip_request_queue(true)
+            .build()
+            .expect("the spec is valid");
+        let body = CreateCustomRewardBody::from(spec);
+        assert_eq!(body.should_redemptions_skip_request_queue, Some(true));
+    }
+
+    /// Answers every request with a Helix 404 and remembers the method and URL
+    #[cfg(feature = "surf")]
+    #[derive(Clone, Default)]
+    struct NotFoundStub {
+        requests: std::sync::Arc<std::sync::Mutex<Vec<(surf::http::Method, surf::Url)>>>,
+    }
+
+    #[cfg(feature = "surf")]
+    #[surf::utils::async_trait]
+    impl surf::middleware::Middleware for NotFoundStub {
+        async fn handle(
+            &self,
+            req: surf::Request,
+            _client: surf::Client,
+            _next: surf::middleware::Next<'_>,
+        ) -> surf::Result<surf::Response> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((req.method(), req.url().clone()));
+            let mut res = surf::http::Response::new(surf::StatusCode::NotFound);
+            res.set_body(serde_json::json!({
+                "error": "Not Found",
+                "status": 404,
+                "message": "Custom Reward Not Found"
+            }));
+            Ok(res.into())
+        }
+    }
+
+    #[cfg(feature = "surf")]
+    #[tokio::test]
+    async fn deleting_a_missing_reward_succeeds() {
+        let stub = NotFoundStub::default();
+        let client = ApiClientBuilder::new(TokenSource::Raw("token".to_string()))
+            .unchecked_token(
+                "client-id",
+                "torpedo09",
+                "274637212",
+                &[Scope::ChannelManageRedemptions],
+            )
+            .required_scopes(&[])
+            .http_client(surf::Client::new().with(stub.clone()))
+            .build()
+            .await
+            .expect("the client is built without asking Twitch");
+
+        let broadcaster_id: UserId = "274637212".to_string().into();
+        let reward_id: RewardId = "reward-id".to_string().into();
+        let result = client
+            .delete_custom_reward(&broadcaster_id, &reward_id)
+            .await;
+        assert!(result.is_ok(), "{:?}", result);
+
+        let requests = stub.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        let (method, url) = &requests[0];
+        assert_eq!(*method, surf::http::Method::Delete);
+        assert!(url.path().ends_with("/channel_points/custom_rewards"));
+        let query: Vec<_> = url.query_pairs().into_owned().collect();
+        assert!(query.contains(&("broadcaster_id".to_string(), "274637212".to_string())));
+        assert!(query.contains(&("id".to_string(), "reward-id".to_string())));
+    }
+}
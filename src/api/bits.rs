@@ -0,0 +1,72 @@
+use http::Method;
+use serde::Deserialize;
+use std::collections::HashMap;
+use twitch_api2::types::UserId;
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// A cheermote, used in chat to cheer bits
+#[derive(Deserialize, Debug, Clone)]
+pub struct Cheermote {
+    /// the text in chat before the number of bits, e.g. `Cheer`
+    pub prefix: String,
+    pub tiers: Vec<CheermoteTier>,
+    /// `global_first_party`, `global_third_party`, `channel_custom`, `display_only` or `sponsored`
+    #[serde(rename = "type")]
+    pub cheermote_type: String,
+    pub order: u32,
+    pub last_updated: String,
+    pub is_charitable: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CheermoteTier {
+    /// the bits needed to reach this tier
+    pub min_bits: u64,
+    pub id: String,
+    /// hex color with # prefix
+    pub color: String,
+    pub images: CheermoteImages,
+    /// whether the tier can be used in chat
+    pub can_cheer: bool,
+    pub show_in_bits_card: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CheermoteImages {
+    pub dark: CheermoteImageSet,
+    pub light: CheermoteImageSet,
+}
+
+/// The image urls by their scale, e.g. `1` or `1.5`
+#[derive(Deserialize, Debug, Clone)]
+pub struct CheermoteImageSet {
+    pub animated: HashMap<String, String>,
+    #[serde(rename = "static")]
+    pub still: HashMap<String, String>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns the cheermotes usable in the channel of a broadcaster,
+    /// or the global cheermotes if `broadcaster_id` is `None`.
+    pub async fn get_cheermotes(
+        &self,
+        broadcaster_id: Option<&UserId>,
+    ) -> Result<Vec<Cheermote>, TwitchCtlError> {
+        let query: Vec<_> = broadcaster_id
+            .map(|id| ("broadcaster_id", id.to_string()))
+            .into_iter()
+            .collect();
+        Ok(self
+            .helix_data::<Vec<Cheermote>, _>(
+                Operation::GetCheermotes,
+                Method::GET,
+                "bits/cheermotes",
+                &query,
+                NO_BODY,
+            )
+            .await?
+            .data)
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+use twitch_api2::twitch_oauth2::Scope;
+
+use super::{
+    helix::parse_base_url, ApiClient, HttpClient, RetryPolicy, TokenRefresh, TokenSource,
+    UncheckedToken, DEFAULT_LOCALE, DEFAULT_MAX_IN_FLIGHT, DEFAULT_REQUEST_TIMEOUT,
+    DEFAULT_REQUIRED_SCOPES,
+};
+use crate::error::TwitchCtlError;
+
+/// Creates an [`ApiClient`] with settings other than the defaults
+pub struct ApiClientBuilder {
+    token: TokenSource,
+    refresh: Option<TokenRefresh>,
+    required_scopes: Vec<Scope>,
+    http_client: Option<HttpClient>,
+    retry_policy: RetryPolicy,
+    request_timeout: Option<Duration>,
+    cache_ttl: Option<Duration>,
+    default_locale: String,
+    confirm: Option<Box<dyn Fn(&str) -> bool + Send + Sync>>,
+    base_url: Option<String>,
+    max_in_flight: usize,
+    unchecked: Option<UncheckedToken>,
+    dry_run: bool,
+}
+
+impl ApiClientBuilder {
+    pub fn new(token: TokenSource) -> Self {
+        ApiClientBuilder {
+            token,
+            refresh: None,
+            required_scopes: DEFAULT_REQUIRED_SCOPES.to_vec(),
+            http_client: None,
+            retry_policy: RetryPolicy::default(),
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+            cache_ttl: None,
+            default_locale: DEFAULT_LOCALE.to_string(),
+            confirm: None,
+            base_url: None,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            unchecked: None,
+            dry_run: false,
+        }
+    }
+
+    /// Refresh the token automatically once it expires
+    pub fn refresh(mut self, refresh: TokenRefresh) -> Self {
+        self.refresh = Some(refresh);
+        self
+    }
+
+    /// The scopes the token has to have, defaults to [`DEFAULT_REQUIRED_SCOPES`]
+    pub fn required_scopes(mut self, required_scopes: &[Scope]) -> Self {
+        self.required_scopes = required_scopes.to_vec();
+        self
+    }
+
+    /// See [`ApiClient::with_http_client`]
+    pub fn http_client(mut self, http_client: HttpClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// See [`ApiClient::with_retry_policy`]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// See [`ApiClient::with_request_timeout`]
+    pub fn request_timeout(mut self, request_timeout: Option<Duration>) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// See [`ApiClient::with_cache`]
+    pub fn cache(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// See [`ApiClient::with_default_locale`]
+    pub fn default_locale(mut self, locale: &str) -> Self {
+        self.default_locale = locale.to_string();
+        self
+    }
+
+    /// See [`ApiClient::with_confirm`]
+    pub fn confirm<F>(mut self, confirm: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.confirm = Some(Box::new(confirm));
+        self
+    }
+
+    /// See [`ApiClient::with_base_url`], fails when building if it is not a valid URL
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// See [`ApiClient::with_max_in_flight`]
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    /// Trusts the token to belong to `login` with the id `user_id` and to have `scopes`,
+    /// instead of validating it with Twitch.
+    ///
+    /// Together with [`ApiClientBuilder::base_url`] no request reaches Twitch, e.g. in tests
+    /// against a local mock server. `client_id` is sent with every request.
+    pub fn unchecked_token(
+        mut self,
+        client_id: &str,
+        login: &str,
+        user_id: &str,
+        scopes: &[Scope],
+    ) -> Self {
+        self.unchecked = Some(UncheckedToken {
+            client_id: client_id.to_string(),
+            login: login.to_string(),
+            user_id: user_id.to_string(),
+            scopes: scopes.to_vec(),
+        });
+        self
+    }
+
+    /// See [`ApiClient::with_dry_run`]
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Loads and validates the token and creates the client.
+    pub async fn build<'a>(self) -> Result<ApiClient<'a>, TwitchCtlError> {
+        // fail before anything is sent if the base URL is invalid
+        if let Some(base_url) = &self.base_url {
+            parse_base_url(base_url)?;
+        }
+        let token = self.token.resolve()?;
+        let http_client = self.http_client.unwrap_or_else(HttpClient::new);
+        let client = match self.unchecked {
+            Some(unchecked) => ApiClient::connect_unchecked(
+                &token,
+                self.refresh,
+                &self.required_scopes,
+                http_client,
+                unchecked,
+            )?,
+            None => {
+                ApiClient::connect(&token, self.refresh, &self.required_scopes, http_client).await?
+            }
+        };
+        let client = client
+            .with_retry_policy(self.retry_policy)
+            .with_request_timeout(self.request_timeout)
+            .with_default_locale(&self.default_locale)
+            .with_max_in_flight(self.max_in_flight)
+            .with_dry_run(self.dry_run);
+        let client = match &self.base_url {
+            Some(base_url) => client.with_base_url(base_url)?,
+            None => client,
+        };
+        let client = match self.cache_ttl {
+            Some(ttl) => client.with_cache(ttl),
+            None => client,
+        };
+        Ok(match self.confirm {
+            Some(confirm) => client.with_confirm(confirm),
+            None => client,
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::ApiClient;
+use crate::error::TwitchCtlError;
+
+tokio::task_local! {
+    static BYPASS_CACHE: bool;
+}
+
+/// Responses of read requests, kept for a fixed time
+pub(crate) struct ResponseCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Box<dyn Any + Send + Sync>)>>,
+}
+
+impl ResponseCache {
+    fn get<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        let mut entries = self.entries.lock().expect("lock is not poisoned");
+        match entries.get(key) {
+            Some((fetched, _)) if fetched.elapsed() >= self.ttl => {
+                entries.remove(key);
+                None
+            }
+            Some((_, value)) => value.downcast_ref().cloned(),
+            None => None,
+        }
+    }
+
+    fn insert<T: Send + Sync + 'static>(&self, key: String, value: T) {
+        self.entries
+            .lock()
+            .expect("lock is not poisoned")
+            .insert(key, (Instant::now(), Box::new(value)));
+    }
+
+    fn remove_prefixed(&self, prefix: &str) {
+        self.entries
+            .lock()
+            .expect("lock is not poisoned")
+            .retain(|key, _| !key.starts_with(prefix));
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Keeps the responses of `get_channel_information`, `get_stream` and `search_categories`
+    /// for `ttl`, instead of asking Twitch again.
+    ///
+    /// Changing the channel information removes it from the cache.
+    pub fn with_cache(mut self, ttl: Duration) -> Self {
+        self.cache = Some(ResponseCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        });
+        self
+    }
+
+    /// Runs `fut` without using cached responses, e.g. `client.uncached(client.get_stream(&id))`.
+    ///
+    /// The fresh responses are still cached for later calls.
+    pub async fn uncached<F: Future>(&self, fut: F) -> F::Output {
+        BYPASS_CACHE.scope(true, fut).await
+    }
+
+    /// Removes all cached responses.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.remove_prefixed("");
+        }
+    }
+
+    /// Returns the cached response for `key`, or caches the result of `fetch`.
+    pub(crate) async fn cached<T, F, Fut>(&self, key: String, fetch: F) -> Result<T, TwitchCtlError>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, TwitchCtlError>>,
+    {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return fetch().await,
+        };
+        let bypass = BYPASS_CACHE.try_with(|bypass| *bypass).unwrap_or(false);
+        if !bypass {
+            if let Some(value) = cache.get(&key) {
+                return Ok(value);
+            }
+        }
+        let value = fetch().await?;
+        cache.insert(key, value.clone());
+        Ok(value)
+    }
+
+    /// Removes the cached responses with keys starting with `prefix`.
+    pub(crate) fn invalidate_cache(&self, prefix: &str) {
+        if let Some(cache) = &self.cache {
+            cache.remove_prefixed(prefix);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use http::Method;
+use serde::Deserialize;
+use twitch_api2::types::UserId;
+
+use super::{
+    helix::{Page, Paged, NO_BODY},
+    ApiClient, Operation,
+};
+use crate::error::TwitchCtlError;
+
+/// A user following a channel
+#[derive(Deserialize, Debug, Clone)]
+pub struct Follower {
+    pub user_id: UserId,
+    pub user_name: String,
+    pub followed_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelFollowers {
+    /// the total number of followers
+    pub total: u64,
+    pub followers: Vec<Follower>,
+}
+
+/// A goal for followers, subscriptions or other things set by the broadcaster
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreatorGoal {
+    pub id: String,
+    /// e.g. `follower`, `subscription` or `new_subscription`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub description: String,
+    pub current_amount: u64,
+    pub target_amount: u64,
+}
+
+/// A channel found by [`ApiClient::search_channels`]
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChannelSearchResult {
+    pub id: UserId,
+    pub broadcaster_login: String,
+    pub display_name: String,
+    pub broadcaster_language: String,
+    /// the id of the category of the current or last stream
+    pub game_id: String,
+    pub game_name: String,
+    pub title: String,
+    pub is_live: bool,
+    /// empty if the channel is offline
+    pub started_at: String,
+    pub thumbnail_url: String,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns up to `max` channels whose name matches `query`, the best matches first.
+    ///
+    /// With `live_only` offline channels are left out.
+    pub async fn search_channels(
+        &self,
+        query: &str,
+        live_only: bool,
+        max: usize,
+    ) -> Result<Vec<ChannelSearchResult>, TwitchCtlError> {
+        let mut channels = self
+            .helix_pages(
+                Operation::SearchChannels,
+                "search/channels",
+                &[
+                    ("query", query.to_string()),
+                    ("live_only", live_only.to_string()),
+                ],
+                Some(max.max(1)),
+            )
+            .await?
+            .data;
+        channels.truncate(max.max(1));
+        Ok(channels)
+    }
+
+    /// Returns the follower count and the most recent followers of a channel.
+    ///
+    /// When `all` is set every follower is fetched instead of only the first 100.
+    pub async fn get_channel_followers(
+        &self,
+        broadcaster_id: &UserId,
+        all: bool,
+    ) -> Result<ChannelFollowers, TwitchCtlError> {
+        let max = if all { None } else { Some(100) };
+        let res = self
+            .helix_pages(
+                Operation::GetChannelFollowers,
+                "channels/followers",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                max,
+            )
+            .await?;
+        Ok(ChannelFollowers {
+            total: res.total.unwrap_or(res.data.len() as u64),
+            followers: res.data,
+        })
+    }
+
+    /// Returns a page of the followers of a channel, the most recent first.
+    pub async fn get_channel_followers_page(
+        &self,
+        broadcaster_id: &UserId,
+        page: &Page,
+    ) -> Result<Paged<Follower>, TwitchCtlError> {
+        self.helix_page(
+            Operation::GetChannelFollowers,
+            "channels/followers",
+            &[("broadcaster_id", broadcaster_id.to_string())],
+            page,
+        )
+        .await
+    }
+
+    /// Returns when `user_id` followed the channel, or `None` if they do not follow it.
+    pub async fn get_followed_at(
+        &self,
+        broadcaster_id: &UserId,
+        user_id: &UserId,
+    ) -> Result<Option<String>, TwitchCtlError> {
+        let followers = self
+            .helix_pages::<Follower>(
+                Operation::GetChannelFollowers,
+                "channels/followers",
+                &[
+                    ("broadcaster_id", broadcaster_id.to_string()),
+                    ("user_id", user_id.to_string()),
+                ],
+                Some(1),
+            )
+            .await?
+            .data;
+        Ok(followers.into_iter().next().map(|f| f.followed_at))
+    }
+
+    /// Returns the active goals of a broadcaster, which is empty if none are set.
+    pub async fn get_creator_goals(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<CreatorGoal>, TwitchCtlError> {
+        Ok(self
+            .helix_data(
+                Operation::GetCreatorGoals,
+                Method::GET,
+                "goals",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                NO_BODY,
+            )
+            .await?
+            .data)
+    }
+}
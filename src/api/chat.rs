@@ -0,0 +1,246 @@
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use twitch_api2::types::UserId;
+
+use super::{helix::NO_BODY, ApiClient, Operation, UserIdent};
+use crate::error::TwitchCtlError;
+
+/// The color used to highlight an announcement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnouncementColor {
+    Blue,
+    Green,
+    Orange,
+    Purple,
+    /// the channel's accent color
+    Primary,
+}
+
+impl AnnouncementColor {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementColor::Blue => "blue",
+            AnnouncementColor::Green => "green",
+            AnnouncementColor::Orange => "orange",
+            AnnouncementColor::Purple => "purple",
+            AnnouncementColor::Primary => "primary",
+        }
+    }
+}
+
+impl FromStr for AnnouncementColor {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blue" => Ok(AnnouncementColor::Blue),
+            "green" => Ok(AnnouncementColor::Green),
+            "orange" => Ok(AnnouncementColor::Orange),
+            "purple" => Ok(AnnouncementColor::Purple),
+            "primary" => Ok(AnnouncementColor::Primary),
+            _ => Err("unsupported announcement color"),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WhisperBody<'s> {
+    message: &'s str,
+}
+
+#[derive(Serialize)]
+struct AnnouncementBody<'s> {
+    message: &'s str,
+    color: &'static str,
+}
+
+/// The chat modes of a channel
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChatSettings {
+    pub slow_mode: bool,
+    /// seconds a user has to wait between messages
+    pub slow_mode_wait_time: Option<u32>,
+    pub follower_mode: bool,
+    /// minutes a user has to follow before chatting
+    pub follower_mode_duration: Option<u32>,
+    pub subscriber_mode: bool,
+    pub emote_mode: bool,
+    pub unique_chat_mode: bool,
+}
+
+/// Changes to the chat modes, only the fields that are set are changed
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ChatSettingsPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_mode: Option<bool>,
+    /// between 3 and 120 seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slow_mode_wait_time: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower_mode: Option<bool>,
+    /// between 0 minutes and 3 months
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower_mode_duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscriber_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emote_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_chat_mode: Option<bool>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Sends an announcement to the chat of a broadcaster as the token user.
+    pub async fn send_announcement(
+        &self,
+        broadcaster_id: &UserId,
+        message: &str,
+        color: AnnouncementColor,
+    ) -> Result<(), TwitchCtlError> {
+        // Twitch silently truncates longer messages
+        if message.chars().count() > 500 {
+            return Err(TwitchCtlError::Validation(
+                "Announcements can not be longer than 500 characters.".to_string(),
+            ));
+        }
+        self.helix_send(
+            Operation::SendAnnouncement,
+            Method::POST,
+            "chat/announcements",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            Some(&AnnouncementBody {
+                message,
+                color: color.as_str(),
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Gives a shoutout to another broadcaster, as the token user.
+    ///
+    /// Twitch only allows one shoutout every 2 minutes, and one per target every 60 minutes,
+    /// otherwise this fails with [`TwitchCtlError::ShoutoutCooldown`].
+    pub async fn send_shoutout(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let to_broadcaster_id = self.get_broadcaster_id(to).await?;
+        self.helix_send(
+            Operation::SendShoutout,
+            Method::POST,
+            "chat/shoutouts",
+            &[
+                ("from_broadcaster_id", from_broadcaster_id.to_string()),
+                ("to_broadcaster_id", to_broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChatSettings, TwitchCtlError> {
+        self.helix_data::<Vec<ChatSettings>, _>(
+            Operation::GetChatSettings,
+            Method::GET,
+            "chat/settings",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            TwitchCtlError::NotFound(format!("No chat settings found for `{}`.", broadcaster_id))
+        })
+    }
+
+    /// Changes the chat modes of a broadcaster, leaving the modes not set in `settings` untouched.
+    pub async fn update_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+        settings: ChatSettingsPatch,
+    ) -> Result<(), TwitchCtlError> {
+        if let Some(wait_time) = settings.slow_mode_wait_time {
+            if !(3..=120).contains(&wait_time) {
+                return Err(TwitchCtlError::Validation(format!(
+                    "The slow mode wait time has to be 3 to 120 seconds, got {}.",
+                    wait_time
+                )));
+            }
+        }
+        self.helix_send(
+            Operation::UpdateChatSettings,
+            Method::PATCH,
+            "chat/settings",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            Some(&settings),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Whispers a message to a user as the token user.
+    ///
+    /// Messages can have up to 10000 characters, or 500 when `new_conversation` is set, as
+    /// Twitch truncates the first message to a user. Twitch does not tell whether the token user
+    /// whispered to `to_user` before, so the caller has to.
+    pub async fn send_whisper(
+        &self,
+        to_user: UserIdent,
+        message: &str,
+        new_conversation: bool,
+    ) -> Result<(), TwitchCtlError> {
+        let max_length = if new_conversation { 500 } else { 10000 };
+        if message.chars().count() > max_length {
+            return Err(TwitchCtlError::Validation(format!(
+                "{} can not be longer than {} characters.",
+                if new_conversation {
+                    "The first whisper to a user"
+                } else {
+                    "Whispers"
+                },
+                max_length
+            )));
+        }
+        let to_user_id = self.get_broadcaster_id(to_user).await?;
+        self.helix_send(
+            Operation::SendWhisper,
+            Method::POST,
+            "whispers",
+            &[
+                ("from_user_id", self.get_user_id()?.to_string()),
+                ("to_user_id", to_user_id.to_string()),
+            ],
+            Some(&WhisperBody { message }),
+        )
+        .await
+        .map_err(|e| match e {
+            TwitchCtlError::Auth(message) if message.contains("verified phone") => {
+                TwitchCtlError::WhisperPhoneNotVerified
+            }
+            TwitchCtlError::RateLimited { retry_after } => {
+                TwitchCtlError::WhisperRateLimited { retry_after }
+            }
+            e => e,
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use http::Method;
+use serde::Deserialize;
+use twitch_api2::types::{CategoryId, UserId};
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// A clip that is still being processed by Twitch
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreatedClip {
+    pub id: String,
+    /// where the title and length of the clip can be changed
+    pub edit_url: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Clip {
+    pub id: String,
+    pub url: String,
+    pub broadcaster_name: String,
+    pub creator_name: String,
+    /// the category the broadcaster streamed when the clip was created
+    pub game_id: CategoryId,
+    pub title: String,
+    pub view_count: u64,
+    pub created_at: String,
+    /// the length in seconds
+    pub duration: f64,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Clips the last seconds of a live stream.
+    ///
+    /// With `has_delay` the clip starts a few seconds earlier, to account for the stream delay.
+    /// Twitch processes the clip in the background, use [`ApiClient::get_clip`]
+    /// to check whether it is available. Returns `None` in a dry run.
+    pub async fn create_clip(
+        &self,
+        broadcaster_id: &UserId,
+        has_delay: bool,
+    ) -> Result<Option<CreatedClip>, TwitchCtlError> {
+        self.helix_write::<Vec<CreatedClip>, _>(
+            Operation::CreateClip,
+            Method::POST,
+            "clips",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("has_delay", has_delay.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await
+        .map_err(|e| match e {
+            TwitchCtlError::NotFound(_) => TwitchCtlError::NotLive("create a clip"),
+            e => e,
+        })?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the created clip".into())
+            })
+        })
+        .transpose()
+    }
+
+    /// Returns up to `max` clips of a broadcaster, the most viewed first.
+    pub async fn get_broadcaster_clips(
+        &self,
+        broadcaster_id: &UserId,
+        max: usize,
+    ) -> Result<Vec<Clip>, TwitchCtlError> {
+        let mut clips = self
+            .helix_pages(
+                Operation::GetClips,
+                "clips",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                Some(max),
+            )
+            .await?
+            .data;
+        clips.truncate(max);
+        Ok(clips)
+    }
+
+    /// Returns a clip, or `None` if it does not exist or is not processed yet.
+    pub async fn get_clip(&self, clip_id: &str) -> Result<Option<Clip>, TwitchCtlError> {
+        Ok(self
+            .helix_data::<Vec<Clip>, _>(
+                Operation::GetClips,
+                Method::GET,
+                "clips",
+                &[("id", clip_id.to_string())],
+                NO_BODY,
+            )
+            .await?
+            .data
+            .into_iter()
+            .next())
+    }
+}
@@ -0,0 +1,91 @@
+use http::Method;
+use serde::Deserialize;
+use twitch_api2::types::{EmoteSetId, UserId};
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// An emote usable in chat
+#[derive(Deserialize, Debug, Clone)]
+pub struct Emote {
+    pub id: String,
+    pub name: String,
+    pub images: EmoteImages,
+    /// `1000`, `2000` or `3000` for subscriber emotes, empty otherwise
+    #[serde(default)]
+    pub tier: String,
+    /// `subscriptions`, `bitstier` or `follower`, empty for global emotes
+    #[serde(default)]
+    pub emote_type: String,
+    /// `None` for global emotes
+    pub emote_set_id: Option<EmoteSetId>,
+    /// the id of the broadcaster the emote belongs to, only set for emote sets
+    pub owner_id: Option<UserId>,
+}
+
+/// The image urls of an emote in the three sizes
+#[derive(Deserialize, Debug, Clone)]
+pub struct EmoteImages {
+    pub url_1x: String,
+    pub url_2x: String,
+    pub url_4x: String,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns the custom emotes of a broadcaster.
+    pub async fn get_channel_emotes(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<Emote>, TwitchCtlError> {
+        Ok(self
+            .helix_data::<Vec<Emote>, _>(
+                Operation::GetEmotes,
+                Method::GET,
+                "chat/emotes",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                NO_BODY,
+            )
+            .await?
+            .data)
+    }
+
+    /// Returns the emotes everybody can use.
+    pub async fn get_global_emotes(&self) -> Result<Vec<Emote>, TwitchCtlError> {
+        Ok(self
+            .helix_data::<Vec<Emote>, _>(
+                Operation::GetEmotes,
+                Method::GET,
+                "chat/emotes/global",
+                &[],
+                NO_BODY,
+            )
+            .await?
+            .data)
+    }
+
+    /// Returns the emotes of the emote sets.
+    pub async fn get_emote_sets(
+        &self,
+        set_ids: &[&EmoteSetId],
+    ) -> Result<Vec<Emote>, TwitchCtlError> {
+        let mut emotes = vec![];
+        // Twitch accepts up to 25 ids per request
+        for set_ids in set_ids.chunks(25) {
+            let query: Vec<_> = set_ids
+                .iter()
+                .map(|id| ("emote_set_id", id.to_string()))
+                .collect();
+            let mut res = self
+                .helix_data::<Vec<Emote>, _>(
+                    Operation::GetEmotes,
+                    Method::GET,
+                    "chat/emotes/set",
+                    &query,
+                    NO_BODY,
+                )
+                .await?;
+            emotes.append(&mut res.data);
+        }
+        Ok(emotes)
+    }
+}
@@ -0,0 +1,229 @@
+//! Receiving events through the EventSub WebSocket, only available with the `eventsub` feature
+
+use futures::StreamExt;
+use http::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use super::{ApiClient, Operation};
+use crate::error::TwitchCtlError;
+use crate::warning;
+
+const EVENTSUB_URL: &str = "wss://eventsub.wss.twitch.tv/ws";
+
+/// Time on top of the keepalive timeout before the session is considered lost
+const KEEPALIVE_MARGIN: Duration = Duration::from_secs(5);
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+struct Subscription {
+    kind: String,
+    version: String,
+    condition: Value,
+    handler: Box<dyn FnMut(Value) + Send>,
+}
+
+/// Subscribes to EventSub events and calls the registered handlers with them.
+///
+/// ```ignore
+/// EventSubClient::new(&client)
+///     .on(
+///         "channel.channel_points_custom_reward_redemption.add",
+///         "1",
+///         json!({ "broadcaster_user_id": client.get_user_id()? }),
+///         |event| println!("{}", event),
+///     )
+///     .run()
+///     .await?;
+/// ```
+pub struct EventSubClient<'c, 'a> {
+    client: &'c ApiClient<'a>,
+    subscriptions: Vec<Subscription>,
+}
+
+#[derive(Deserialize)]
+struct EventSubMessage {
+    metadata: MessageMetadata,
+    #[serde(default)]
+    payload: MessagePayload,
+}
+
+#[derive(Deserialize)]
+struct MessageMetadata {
+    message_type: String,
+    subscription_type: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct MessagePayload {
+    session: Option<Session>,
+    event: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct Session {
+    id: String,
+    keepalive_timeout_seconds: Option<u64>,
+    reconnect_url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SubscriptionBody<'s> {
+    #[serde(rename = "type")]
+    kind: &'s str,
+    version: &'s str,
+    condition: &'s Value,
+    transport: Transport<'s>,
+}
+
+#[derive(Serialize)]
+struct Transport<'s> {
+    method: &'static str,
+    session_id: &'s str,
+}
+
+impl<'c, 'a> EventSubClient<'c, 'a> {
+    /// Creates the subscriptions with the token of `client`.
+    pub fn new(client: &'c ApiClient<'a>) -> Self {
+        EventSubClient {
+            client,
+            subscriptions: vec![],
+        }
+    }
+
+    /// Calls `handler` with the event of every notification for the subscription type `kind`.
+    ///
+    /// `condition` is the condition object Twitch expects for this type and `version`.
+    pub fn on<F>(mut self, kind: &str, version: &str, condition: Value, handler: F) -> Self
+    where
+        F: FnMut(Value) + Send + 'static,
+    {
+        self.subscriptions.push(Subscription {
+            kind: kind.to_string(),
+            version: version.to_string(),
+            condition,
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    /// Connects to EventSub and handles the events until an error occurs.
+    ///
+    /// When Twitch asks to reconnect, the subscriptions are moved to the new connection.
+    /// When the connection is lost, a new one is opened and the subscriptions are created again.
+    pub async fn run(mut self) -> Result<(), TwitchCtlError> {
+        let mut socket = connect(EVENTSUB_URL).await?;
+        // still read until the new connection is welcomed, Twitch sends on it until then
+        let mut previous_socket: Option<Socket> = None;
+        let mut subscribed = false;
+        let mut keepalive = Duration::from_secs(10) + KEEPALIVE_MARGIN;
+        loop {
+            let next = tokio::time::timeout(keepalive, async {
+                match previous_socket.as_mut() {
+                    // the old connection first, its notifications were sent before the welcome
+                    Some(previous) => tokio::select! {
+                        biased;
+                        message = previous.next() => (message, true),
+                        message = socket.next() => (message, false),
+                    },
+                    None => (socket.next().await, false),
+                }
+            })
+            .await;
+            let text = match next {
+                Ok((Some(Ok(Message::Text(text))), _)) => text,
+                Ok((Some(Ok(Message::Close(_))), true))
+                | Ok((Some(Err(_)), true))
+                | Ok((None, true)) => {
+                    previous_socket = None;
+                    continue;
+                }
+                Ok((Some(Ok(Message::Close(_))), false)) | Ok((None, false)) | Err(_) => {
+                    warning!("Lost the EventSub connection, reconnecting");
+                    socket = connect(EVENTSUB_URL).await?;
+                    previous_socket = None;
+                    subscribed = false;
+                    continue;
+                }
+                Ok((Some(Ok(_)), _)) => continue,
+                Ok((Some(Err(e)), false)) => return Err(TwitchCtlError::EventSub(Box::new(e))),
+            };
+            let message: EventSubMessage =
+                serde_json::from_str(&text).map_err(|e| TwitchCtlError::EventSub(Box::new(e)))?;
+            match message.metadata.message_type.as_str() {
+                "session_welcome" => {
+                    let session = session(message.payload)?;
+                    keepalive =
+                        Duration::from_secs(session.keepalive_timeout_seconds.unwrap_or(10))
+                            + KEEPALIVE_MARGIN;
+                    previous_socket = None;
+                    if !subscribed {
+                        self.subscribe(&session.id).await?;
+                        subscribed = true;
+                    }
+                }
+                "session_reconnect" => {
+                    let url = session(message.payload)?.reconnect_url.ok_or_else(|| {
+                        TwitchCtlError::EventSub("Twitch did not send a reconnect url".into())
+                    })?;
+                    previous_socket = Some(std::mem::replace(&mut socket, connect(&url).await?));
+                }
+                "notification" => {
+                    let kind = message.metadata.subscription_type.unwrap_or_default();
+                    if let Some(event) = message.payload.event {
+                        for subscription in &mut self.subscriptions {
+                            if subscription.kind == kind {
+                                (subscription.handler)(event.clone());
+                            }
+                        }
+                    }
+                }
+                "revocation" => warning!(
+                    "Twitch revoked the subscription to `{}`",
+                    message.metadata.subscription_type.unwrap_or_default()
+                ),
+                // keepalives only reset the timeout
+                _ => {}
+            }
+        }
+    }
+
+    async fn subscribe(&self, session_id: &str) -> Result<(), TwitchCtlError> {
+        for subscription in &self.subscriptions {
+            self.client
+                .helix_send(
+                    Operation::CreateEventSubSubscription,
+                    Method::POST,
+                    "eventsub/subscriptions",
+                    &[],
+                    Some(&SubscriptionBody {
+                        kind: &subscription.kind,
+                        version: &subscription.version,
+                        condition: &subscription.condition,
+                        transport: Transport {
+                            method: "websocket",
+                            session_id,
+                        },
+                    }),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+async fn connect(url: &str) -> Result<Socket, TwitchCtlError> {
+    let (socket, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .map_err(|e| TwitchCtlError::EventSub(Box::new(e)))?;
+    Ok(socket)
+}
+
+fn session(payload: MessagePayload) -> Result<Session, TwitchCtlError> {
+    payload
+        .session
+        .ok_or_else(|| TwitchCtlError::EventSub("Twitch did not send the session".into()))
+}
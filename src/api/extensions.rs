@@ -0,0 +1,86 @@
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use twitch_api2::types::UserId;
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// The extensions in the slots of a channel, by the number of the slot, e.g. `1`
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ActiveExtensions {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub panel: BTreeMap<String, ExtensionSlot>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overlay: BTreeMap<String, ExtensionSlot>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub component: BTreeMap<String, ExtensionSlot>,
+}
+
+/// A slot for an extension, the other fields are only set if it is `active`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ExtensionSlot {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// only returned by Twitch
+    #[serde(skip_serializing)]
+    pub name: Option<String>,
+    /// the horizontal position of a component in percent of the video
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<u32>,
+    /// the vertical position of a component in percent of the video
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct ExtensionsBody<'s> {
+    data: &'s ActiveExtensions,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns the active extensions of a user, or of the token user if `user_id` is `None`.
+    pub async fn get_user_active_extensions(
+        &self,
+        user_id: Option<&UserId>,
+    ) -> Result<ActiveExtensions, TwitchCtlError> {
+        let query: Vec<_> = user_id
+            .map(|id| ("user_id", id.to_string()))
+            .into_iter()
+            .collect();
+        Ok(self
+            .helix_data::<ActiveExtensions, _>(
+                Operation::GetUserExtensions,
+                Method::GET,
+                "users/extensions",
+                &query,
+                NO_BODY,
+            )
+            .await?
+            .data)
+    }
+
+    /// Activates, deactivates or moves the extensions of the token user and returns the
+    /// extensions afterwards, or `None` in a dry run.
+    ///
+    /// Slots left out of `config` are not changed. The token needs the `user:edit:broadcast`
+    /// scope.
+    pub async fn update_user_extensions(
+        &self,
+        config: &ActiveExtensions,
+    ) -> Result<Option<ActiveExtensions>, TwitchCtlError> {
+        Ok(self
+            .helix_write::<ActiveExtensions, _>(
+                Operation::ManageExtensions,
+                Method::PUT,
+                "users/extensions",
+                &[],
+                Some(&ExtensionsBody { data: config }),
+            )
+            .await?
+            .map(|res| res.data))
+    }
+}
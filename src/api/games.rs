@@ -0,0 +1,196 @@
+use futures::{stream, StreamExt, TryStreamExt};
+use std::collections::{HashMap, HashSet};
+use twitch_api2::{
+    helix::{
+        games::{Game, GetGamesRequest, GetTopGamesRequest},
+        search::search_categories::Category,
+    },
+    types::{CategoryId, UserId},
+};
+
+use super::{ApiClient, Clip, Operation, Video, VideoType};
+use crate::error::TwitchCtlError;
+
+/// How many of the most recent past broadcasts [`ApiClient::recent_categories`] looks at
+const RECENT_BROADCASTS: usize = 20;
+
+/// The largest width and height of box art Twitch is asked for
+pub const MAX_BOX_ART_SIZE: u32 = 2000;
+
+/// The url of the box art of `category` in the size `width` x `height`.
+///
+/// Twitch returns either a template with `{width}x{height}` or a url of a fixed size like
+/// `-52x72.jpg`, both are replaced. Sizes of 0 or above [`MAX_BOX_ART_SIZE`] are rejected.
+pub fn box_art_url(category: &Category, width: u32, height: u32) -> Result<String, TwitchCtlError> {
+    for size in [width, height].iter() {
+        if *size == 0 || *size > MAX_BOX_ART_SIZE {
+            return Err(TwitchCtlError::Validation(format!(
+                "box art sizes must be between 1 and {}, got {}x{}",
+                MAX_BOX_ART_SIZE, width, height
+            )));
+        }
+    }
+    let url = &category.box_art_url;
+    if url.contains("{width}") || url.contains("{height}") {
+        return Ok(url
+            .replace("{width}", &width.to_string())
+            .replace("{height}", &height.to_string()));
+    }
+    // e.g. `https://static-cdn.jtvnw.net/ttv-boxart/33214-52x72.jpg`
+    let is_size = |size: &str| {
+        let mut parts = size.splitn(2, 'x');
+        matches!(
+            (parts.next(), parts.next()),
+            (Some(w), Some(h)) if !w.is_empty() && !h.is_empty()
+                && w.chars().chain(h.chars()).all(|c| c.is_ascii_digit())
+        )
+    };
+    match (url.rfind('-'), url.rfind('.')) {
+        (Some(dash), Some(dot)) if dash < dot && is_size(&url[dash + 1..dot]) => Ok(format!(
+            "{}-{}x{}{}",
+            &url[..dash],
+            width,
+            height,
+            &url[dot..]
+        )),
+        _ => Ok(url.clone()),
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns up to `max` games, sorted by the number of current viewers.
+    pub async fn get_top_games(&self, max: usize) -> Result<Vec<Game>, TwitchCtlError> {
+        let mut games = vec![];
+        let mut pagination = None;
+        while games.len() < max {
+            let req = GetTopGamesRequest::builder()
+                .after(pagination)
+                .first(Some((max - games.len()).min(100)))
+                .build();
+            let mut res = self.req_get(Operation::GetGames, req).await?;
+            games.append(&mut res.data);
+            pagination = res.pagination;
+            if pagination == None {
+                break;
+            }
+        }
+        games.truncate(max);
+        Ok(games)
+    }
+
+    /// Returns up to `limit` categories a broadcaster streamed in their recent past broadcasts,
+    /// with their name and in how many broadcasts they were streamed, the most frequent first.
+    ///
+    /// Twitch does not tell the category of videos, so the categories of a broadcast are the
+    /// ones of the clips created between its start and the start of the next broadcast. The
+    /// counts come from clips, so a broadcast nobody clipped adds nothing to any category.
+    pub async fn recent_categories(
+        &self,
+        broadcaster_id: &UserId,
+        limit: usize,
+    ) -> Result<Vec<(CategoryId, String, usize)>, TwitchCtlError> {
+        let query = [
+            ("user_id", broadcaster_id.to_string()),
+            ("type", VideoType::Archive.as_str().to_string()),
+        ];
+        let broadcasts = self
+            .helix_pages::<Video>(
+                Operation::GetVideos,
+                "videos",
+                &query,
+                Some(RECENT_BROADCASTS),
+            )
+            .await?
+            .data;
+        // the most recent broadcast is first, so each one ends before the previous one starts
+        let windows: Vec<_> = broadcasts
+            .iter()
+            .enumerate()
+            .map(|(i, video)| {
+                let next = i
+                    .checked_sub(1)
+                    .map(|next| broadcasts[next].created_at.as_str());
+                (video.created_at.as_str(), next)
+            })
+            .collect();
+        let streamed: Vec<HashSet<CategoryId>> = stream::iter(windows)
+            .map(|(started_at, ended_at)| {
+                self.broadcast_categories(broadcaster_id, started_at, ended_at)
+            })
+            .buffered(self.max_in_flight)
+            .try_collect()
+            .await?;
+        let mut counts: HashMap<CategoryId, usize> = HashMap::new();
+        for category in streamed.into_iter().flatten() {
+            *counts.entry(category).or_default() += 1;
+        }
+        let ids: Vec<_> = counts.keys().collect();
+        let mut categories: Vec<_> = self
+            .get_games(&ids, &[])
+            .await?
+            .into_iter()
+            .map(|game| {
+                let count = counts.get(&game.id).copied().unwrap_or_default();
+                (game.id, game.name, count)
+            })
+            .collect();
+        categories.sort_by(|(_, a_name, a), (_, b_name, b)| b.cmp(a).then(a_name.cmp(b_name)));
+        categories.truncate(limit);
+        Ok(categories)
+    }
+
+    /// The categories of the clips a broadcaster created from `started_at` to `ended_at`
+    async fn broadcast_categories(
+        &self,
+        broadcaster_id: &UserId,
+        started_at: &str,
+        ended_at: Option<&str>,
+    ) -> Result<HashSet<CategoryId>, TwitchCtlError> {
+        let mut query = vec![
+            ("broadcaster_id", broadcaster_id.to_string()),
+            ("started_at", started_at.to_string()),
+        ];
+        if let Some(ended_at) = ended_at {
+            query.push(("ended_at", ended_at.to_string()));
+        }
+        let clips = self
+            .helix_pages::<Clip>(Operation::GetClips, "clips", &query, Some(100))
+            .await?
+            .data;
+        Ok(clips
+            .into_iter()
+            .map(|clip| clip.game_id)
+            .filter(|id| !id.as_str().is_empty())
+            .collect())
+    }
+
+    /// Looks up games by their ids or exact names.
+    ///
+    /// Games that do not exist are left out of the result.
+    pub async fn get_games(
+        &self,
+        ids: &[&CategoryId],
+        names: &[&str],
+    ) -> Result<Vec<Game>, TwitchCtlError> {
+        let mut games = vec![];
+        // Twitch accepts up to 100 ids and names per request
+        for ids in ids.chunks(100) {
+            let req = GetGamesRequest::builder()
+                .id(ids.iter().cloned().cloned().collect::<Vec<_>>())
+                .build();
+            games.append(&mut self.req_get(Operation::GetGames, req).await?.data);
+        }
+        for names in names.chunks(100) {
+            let req = GetGamesRequest::builder()
+                .name(
+                    names
+                        .iter()
+                        .map(|name| name.to_string())
+                        .collect::<Vec<_>>(),
+                )
+                .build();
+            games.append(&mut self.req_get(Operation::GetGames, req).await?.data);
+        }
+        Ok(games)
+    }
+}
@@ -0,0 +1,271 @@
+//! Requests to Helix endpoints that twitch_api2 does not support yet
+
+use http::Method;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use twitch_api2::client::{Req, Response};
+use url::Url;
+
+use super::{trace, transport::TransportError, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+const HELIX_URL: &str = "https://api.twitch.tv/helix/";
+
+/// Used as `body` for requests without one
+pub(crate) const NO_BODY: Option<&()> = None;
+
+/// The content of a successful Helix response
+#[derive(Deserialize, Debug)]
+pub(crate) struct HelixData<T> {
+    pub data: T,
+    #[serde(default)]
+    pub pagination: HelixPagination,
+    pub total: Option<u64>,
+    /// only returned for subscriptions
+    pub points: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub(crate) struct HelixPagination {
+    pub cursor: Option<String>,
+}
+
+/// Which part of a list to fetch
+#[derive(Debug, Clone, Default)]
+pub struct Page {
+    /// the maximum number of items, Twitch limits it to 100 or less depending on the endpoint
+    pub first: Option<usize>,
+    /// the cursor of the previous page
+    pub after: Option<String>,
+}
+
+/// A part of a list, see [`Page`]
+#[derive(Debug, Clone)]
+pub struct Paged<T> {
+    pub data: Vec<T>,
+    /// the cursor for fetching the next page, `None` on the last page
+    pub cursor: Option<String>,
+}
+
+/// Sends the Helix requests to another server, see [`ApiClient::with_base_url`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BaseUrl {
+    url: Arc<Mutex<Option<Url>>>,
+}
+
+impl BaseUrl {
+    /// Points `req` at the base url instead, if one is set and `req` is for Helix
+    pub(crate) fn rewrite(&self, req: &mut Req) -> Result<(), TransportError> {
+        let base_url = self.url.lock().expect("lock is not poisoned").clone();
+        if let Some(base_url) = base_url {
+            let uri = req.uri().to_string();
+            if let Some(path) = uri.strip_prefix(HELIX_URL) {
+                *req.uri_mut() = base_url.join(path)?.as_str().parse()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_base_url(base_url: &str) -> Result<Url, TwitchCtlError> {
+    // without the trailing slash joining the endpoints would replace the last segment
+    let base_url = if base_url.ends_with('/') {
+        base_url.to_string()
+    } else {
+        format!("{}/", base_url)
+    };
+    Url::parse(&base_url)
+        .map_err(|e| TwitchCtlError::Validation(format!("Invalid base URL `{}`: {}", base_url, e)))
+}
+
+#[derive(Deserialize)]
+struct HelixErrorBody {
+    message: String,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Sends the Helix requests to `base_url` instead of Twitch, e.g. to a local mock server
+    /// like the one of the Twitch CLI.
+    ///
+    /// The token is still validated with Twitch, unless it is trusted with
+    /// [`ApiClientBuilder::unchecked_token`](super::ApiClientBuilder::unchecked_token).
+    pub fn with_base_url(self, base_url: &str) -> Result<Self, TwitchCtlError> {
+        let base_url = parse_base_url(base_url)?;
+        *self.base_url.url.lock().expect("lock is not poisoned") = Some(base_url);
+        Ok(self)
+    }
+
+    /// Sends a request to `endpoint`, turns error responses into a [`TwitchCtlError`].
+    ///
+    /// Rate limited requests are retried, unless `op` is not idempotent.
+    /// Returns `None` for requests changing something in a dry run.
+    pub(crate) async fn helix_send<B: Serialize>(
+        &self,
+        op: Operation,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+        body: Option<&B>,
+    ) -> Result<Option<Response>, TwitchCtlError> {
+        if method != Method::GET {
+            self.get_user_id()?;
+        }
+        // checking messages with AutoMod is a POST request that changes nothing
+        let changes = method != Method::GET && op != Operation::CheckAutoMod;
+        if self.dry_run && changes {
+            self.log_dry_run(method.as_str(), endpoint, &query, body);
+            return Ok(None);
+        }
+        let mut url = Url::parse(HELIX_URL)
+            .and_then(|url| url.join(endpoint))
+            .map_err(|e| TwitchCtlError::Helix(Box::new(e)))?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let body = body
+            .map(serde_json::to_vec)
+            .transpose()
+            .map_err(|e| TwitchCtlError::Helix(Box::new(e)))?;
+
+        let (method, url, body) = (&method, &url, &body);
+        let broadcaster_id = trace::query_broadcaster_id(query);
+        self.retrying(op, move || {
+            trace::traced(endpoint, broadcaster_id.clone(), async move {
+                let mut req = http::Request::builder()
+                    .method(method.clone())
+                    .uri(url.as_str());
+                {
+                    let token = self.token().await?;
+                    req = req
+                        .header(
+                            "Authorization",
+                            format!("Bearer {}", token.access_token().secret()),
+                        )
+                        .header("Client-Id", token.client_id().as_str());
+                }
+                if body.is_some() {
+                    req = req.header("Content-Type", "application/json");
+                }
+                let req = req
+                    .body(body.clone().unwrap_or_default())
+                    .map_err(|e| TwitchCtlError::Helix(Box::new(e)))?;
+
+                let res = twitch_api2::HttpClient::req(&self.http_client, req)
+                    .await
+                    .map_err(|e| TwitchCtlError::Helix(Box::new(e)))?;
+                let status = res.status();
+                if status.is_success() {
+                    Ok(Some(res))
+                } else {
+                    let message = match serde_json::from_slice::<HelixErrorBody>(res.body()) {
+                        Ok(HelixErrorBody { message }) => message,
+                        Err(_) => status.canonical_reason().unwrap_or_default().to_string(),
+                    };
+                    Err(TwitchCtlError::from_response(status.as_u16(), message))
+                }
+            })
+        })
+        .await
+    }
+
+    /// Sends a request to `endpoint` and parses the returned data.
+    ///
+    /// Fails with [`TwitchCtlError::DryRun`] if the request was not sent, use
+    /// [`ApiClient::helix_write`] for requests changing something.
+    pub(crate) async fn helix_data<T: DeserializeOwned, B: Serialize>(
+        &self,
+        op: Operation,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+        body: Option<&B>,
+    ) -> Result<HelixData<T>, TwitchCtlError> {
+        self.helix_write(op, method, endpoint, query, body)
+            .await?
+            .ok_or(TwitchCtlError::DryRun)
+    }
+
+    /// Sends a request to `endpoint` and parses the returned data, `None` in a dry run.
+    pub(crate) async fn helix_write<T: DeserializeOwned, B: Serialize>(
+        &self,
+        op: Operation,
+        method: Method,
+        endpoint: &str,
+        query: &[(&str, String)],
+        body: Option<&B>,
+    ) -> Result<Option<HelixData<T>>, TwitchCtlError> {
+        match self.helix_send(op, method, endpoint, query, body).await? {
+            Some(res) => serde_json::from_slice(res.body())
+                .map(Some)
+                .map_err(|e| TwitchCtlError::Helix(Box::new(e))),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetches pages of `endpoint` while Twitch returns a cursor, or until `max` items were fetched.
+    ///
+    /// Returns the items of all pages, with the cursor and totals of the last page.
+    pub(crate) async fn helix_pages<T: DeserializeOwned>(
+        &self,
+        op: Operation,
+        endpoint: &str,
+        query: &[(&str, String)],
+        max: Option<usize>,
+    ) -> Result<HelixData<Vec<T>>, TwitchCtlError> {
+        self.helix_pages_sized(op, endpoint, query, max, 100).await
+    }
+
+    /// Like [`ApiClient::helix_pages`], for endpoints returning less than 100 items per page.
+    pub(crate) async fn helix_pages_sized<T: DeserializeOwned>(
+        &self,
+        op: Operation,
+        endpoint: &str,
+        query: &[(&str, String)],
+        max: Option<usize>,
+        page_size: usize,
+    ) -> Result<HelixData<Vec<T>>, TwitchCtlError> {
+        let mut items = vec![];
+        let mut pagination = None;
+        loop {
+            let mut query = query.to_vec();
+            query.push(("first", max.unwrap_or(page_size).min(page_size).to_string()));
+            if let Some(cursor) = pagination {
+                query.push(("after", cursor));
+            }
+            let mut res = self
+                .helix_data::<Vec<T>, _>(op, Method::GET, endpoint, &query, NO_BODY)
+                .await?;
+            items.append(&mut res.data);
+            pagination = res.pagination.cursor.clone();
+            if pagination == None || max.map_or(false, |max| items.len() >= max) {
+                res.data = items;
+                break Ok(res);
+            }
+        }
+    }
+
+    /// Fetches a single page of `endpoint`.
+    pub(crate) async fn helix_page<T: DeserializeOwned>(
+        &self,
+        op: Operation,
+        endpoint: &str,
+        query: &[(&str, String)],
+        page: &Page,
+    ) -> Result<Paged<T>, TwitchCtlError> {
+        let mut query = query.to_vec();
+        if let Some(first) = page.first {
+            query.push(("first", first.to_string()));
+        }
+        if let Some(after) = &page.after {
+            query.push(("after", after.clone()));
+        }
+        let res = self
+            .helix_data::<Vec<T>, _>(op, Method::GET, endpoint, &query, NO_BODY)
+            .await?;
+        Ok(Paged {
+            data: res.data,
+            cursor: res.pagination.cursor,
+        })
+    }
+}
@@ -0,0 +1,1131 @@
+use async_trait::async_trait;
+use std::{collections::HashMap, sync::Mutex};
+use twitch_api2::{
+    helix::{
+        games::Game,
+        points::{
+            CreateCustomRewardBody, CustomReward, CustomRewardRedemption,
+            CustomRewardRedemptionStatus, UpdateCustomRewardBody,
+        },
+        search::search_categories::Category,
+        streams::Stream,
+        tags::TwitchTag,
+    },
+    types::{CategoryId, RedemptionId, RewardId, TagId, UserId, VideoId},
+};
+
+use super::{
+    match_tag, AnnouncementColor, AutoModResult, BlockContext, BlockReason, BlockedTerm,
+    BlockedUser, CategoryMatch, ChannelFollowers, ChannelInfo, ChannelSubscriptions, ChatSettings,
+    ChatSettingsPatch, Commercial, CreatedClip, CreatorGoal, Follower, HypeTrain,
+    NewScheduleSegment, Poll, PollEnd, Prediction, PredictionStatus, Raid, ScheduleSegment,
+    ScheduleSegmentPatch, ShieldModeStatus, StreamMarker, Subscriber, TwitchApi, UserIdent, Video,
+    VideoType,
+};
+use crate::error::TwitchCtlError;
+
+/// A change requested from a [`MockApiClient`]
+#[derive(Debug, Clone)]
+pub enum MockCall {
+    ModifyChannelInformation(UserId, ChannelInfo),
+    CreateCustomReward(UserId, CreateCustomRewardBody),
+    UpdateCustomReward(UserId, RewardId, UpdateCustomRewardBody),
+    DeleteCustomReward(UserId, RewardId),
+    UpdateRedemptionStatus(
+        UserId,
+        RewardId,
+        Vec<RedemptionId>,
+        CustomRewardRedemptionStatus,
+    ),
+    ReplaceStreamTags(UserId, Vec<TagId>),
+    StartCommercial(UserId, u32),
+    SendAnnouncement(UserId, String, AnnouncementColor),
+    SendShoutout(UserId, UserId),
+    UpdateChatSettings(UserId, ChatSettingsPatch),
+    SendWhisper(UserId, String, bool),
+    CreateClip(UserId, bool),
+    AddBlockedTerm(UserId, String),
+    RemoveBlockedTerm(UserId, String),
+    DeleteChatMessage(UserId, String),
+    ClearChat(UserId),
+    SetShieldMode(UserId, bool),
+    AddVip(UserId, UserId),
+    RemoveVip(UserId, UserId),
+    AddModerator(UserId, UserId),
+    RemoveModerator(UserId, UserId),
+    CreatePoll(UserId, String, Vec<String>, u32, Option<u32>),
+    EndPoll(UserId, String, PollEnd),
+    CreatePrediction(UserId, String, Vec<String>, u32),
+    EndPrediction(UserId, String, PredictionStatus, Option<String>),
+    StartRaid(UserId, UserId),
+    CancelRaid(UserId),
+    CreateScheduleSegment(UserId, NewScheduleSegment),
+    UpdateScheduleSegment(UserId, String, ScheduleSegmentPatch),
+    DeleteScheduleSegment(UserId, String),
+    CreateStreamMarker(UserId, Option<String>),
+    BlockUser(UserId, Option<BlockContext>, Option<BlockReason>),
+    UnblockUser(UserId),
+    DeleteVideos(Vec<VideoId>),
+}
+
+/// A [`TwitchApi`] answering with canned responses instead of asking Twitch.
+///
+/// Reads return what was set with the `set_*` methods, changes are recorded and returned by
+/// [`MockApiClient::calls`]. Adding, changing and removing rewards, moderators, VIPs, blocked
+/// terms, blocked users, schedule segments and videos also changes what is read afterwards.
+/// Writes answering with something only Twitch makes, like a clip or a poll, return `None` as
+/// in a dry run.
+#[derive(Default)]
+pub struct MockApiClient {
+    token_user: Mutex<Option<UserId>>,
+    users: Mutex<HashMap<String, UserId>>,
+    channels: Mutex<HashMap<UserId, ChannelInfo>>,
+    categories: Mutex<Vec<Category>>,
+    streams: Mutex<HashMap<UserId, Stream>>,
+    tags: Mutex<HashMap<UserId, Vec<TwitchTag>>>,
+    rewards: Mutex<HashMap<UserId, Vec<CustomReward>>>,
+    redemptions: Mutex<HashMap<RewardId, Vec<CustomRewardRedemption>>>,
+    all_tags: Mutex<Vec<TwitchTag>>,
+    games: Mutex<Vec<Game>>,
+    followers: Mutex<HashMap<UserId, Vec<Follower>>>,
+    goals: Mutex<HashMap<UserId, Vec<CreatorGoal>>>,
+    hype_trains: Mutex<HashMap<UserId, Vec<HypeTrain>>>,
+    subscribers: Mutex<HashMap<UserId, Vec<Subscriber>>>,
+    chat_settings: Mutex<HashMap<UserId, ChatSettings>>,
+    shield_modes: Mutex<HashMap<UserId, ShieldModeStatus>>,
+    blocked_terms: Mutex<HashMap<UserId, Vec<BlockedTerm>>>,
+    moderators: Mutex<HashMap<UserId, Vec<UserId>>>,
+    vips: Mutex<HashMap<UserId, Vec<UserId>>>,
+    polls: Mutex<HashMap<UserId, Vec<Poll>>>,
+    predictions: Mutex<HashMap<UserId, Vec<Prediction>>>,
+    schedules: Mutex<HashMap<UserId, Vec<ScheduleSegment>>>,
+    blocked_users: Mutex<Vec<BlockedUser>>,
+    videos: Mutex<Vec<Video>>,
+    calls: Mutex<Vec<MockCall>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().expect("lock is not poisoned")
+}
+
+impl MockApiClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The user `UserIdent::None` resolves to
+    pub fn set_token_user(&self, user_id: UserId) {
+        *lock(&self.token_user) = Some(user_id);
+    }
+
+    pub fn set_user(&self, login: &str, user_id: UserId) {
+        lock(&self.users).insert(login.to_lowercase(), user_id);
+    }
+
+    pub fn set_channel_information(&self, broadcaster_id: UserId, info: ChannelInfo) {
+        lock(&self.channels).insert(broadcaster_id, info);
+    }
+
+    /// The categories searched by `search_categories`, in the order Twitch would return them
+    pub fn set_categories(&self, categories: Vec<Category>) {
+        *lock(&self.categories) = categories;
+    }
+
+    /// Sets the stream of a user, `None` for offline.
+    pub fn set_stream(&self, user_id: UserId, stream: Option<Stream>) {
+        let mut streams = lock(&self.streams);
+        match stream {
+            Some(stream) => streams.insert(user_id, stream),
+            None => streams.remove(&user_id),
+        };
+    }
+
+    pub fn set_stream_tags(&self, broadcaster_id: UserId, tags: Vec<TwitchTag>) {
+        lock(&self.tags).insert(broadcaster_id, tags);
+    }
+
+    pub fn set_rewards(&self, broadcaster_id: UserId, rewards: Vec<CustomReward>) {
+        lock(&self.rewards).insert(broadcaster_id, rewards);
+    }
+
+    /// The redemptions of a reward, `get_redemptions` filters them by status
+    pub fn set_redemptions(&self, reward_id: RewardId, redemptions: Vec<CustomRewardRedemption>) {
+        lock(&self.redemptions).insert(reward_id, redemptions);
+    }
+
+    /// The tags `get_all_tags` returns and tag names are resolved with
+    pub fn set_all_tags(&self, tags: Vec<TwitchTag>) {
+        *lock(&self.all_tags) = tags;
+    }
+
+    /// The games looked up by `get_games`, the first ones are the top games
+    pub fn set_games(&self, games: Vec<Game>) {
+        *lock(&self.games) = games;
+    }
+
+    /// The followers of a channel, the most recent first
+    pub fn set_followers(&self, broadcaster_id: UserId, followers: Vec<Follower>) {
+        lock(&self.followers).insert(broadcaster_id, followers);
+    }
+
+    pub fn set_creator_goals(&self, broadcaster_id: UserId, goals: Vec<CreatorGoal>) {
+        lock(&self.goals).insert(broadcaster_id, goals);
+    }
+
+    /// The hype trains of a broadcaster, the most recent first
+    pub fn set_hype_train_events(&self, broadcaster_id: UserId, hype_trains: Vec<HypeTrain>) {
+        lock(&self.hype_trains).insert(broadcaster_id, hype_trains);
+    }
+
+    pub fn set_subscribers(&self, broadcaster_id: UserId, subscribers: Vec<Subscriber>) {
+        lock(&self.subscribers).insert(broadcaster_id, subscribers);
+    }
+
+    pub fn set_chat_settings(&self, broadcaster_id: UserId, settings: ChatSettings) {
+        lock(&self.chat_settings).insert(broadcaster_id, settings);
+    }
+
+    pub fn set_shield_mode_status(&self, broadcaster_id: UserId, status: ShieldModeStatus) {
+        lock(&self.shield_modes).insert(broadcaster_id, status);
+    }
+
+    pub fn set_blocked_terms(&self, broadcaster_id: UserId, terms: Vec<BlockedTerm>) {
+        lock(&self.blocked_terms).insert(broadcaster_id, terms);
+    }
+
+    /// The moderators of a channel, their logins are taken from `set_user`
+    pub fn set_moderators(&self, broadcaster_id: UserId, moderators: Vec<UserId>) {
+        lock(&self.moderators).insert(broadcaster_id, moderators);
+    }
+
+    /// The VIPs of a channel, their logins are taken from `set_user`
+    pub fn set_vips(&self, broadcaster_id: UserId, vips: Vec<UserId>) {
+        lock(&self.vips).insert(broadcaster_id, vips);
+    }
+
+    pub fn set_polls(&self, broadcaster_id: UserId, polls: Vec<Poll>) {
+        lock(&self.polls).insert(broadcaster_id, polls);
+    }
+
+    pub fn set_predictions(&self, broadcaster_id: UserId, predictions: Vec<Prediction>) {
+        lock(&self.predictions).insert(broadcaster_id, predictions);
+    }
+
+    pub fn set_schedule(&self, broadcaster_id: UserId, segments: Vec<ScheduleSegment>) {
+        lock(&self.schedules).insert(broadcaster_id, segments);
+    }
+
+    /// The block list of the token user
+    pub fn set_blocked_users(&self, users: Vec<BlockedUser>) {
+        *lock(&self.blocked_users) = users;
+    }
+
+    /// The videos of all users, the most recent first
+    pub fn set_videos(&self, videos: Vec<Video>) {
+        *lock(&self.videos) = videos;
+    }
+
+    /// The changes requested so far, the oldest first
+    pub fn calls(&self) -> Vec<MockCall> {
+        lock(&self.calls).clone()
+    }
+
+    fn record(&self, call: MockCall) {
+        lock(&self.calls).push(call);
+    }
+
+    /// The login set with `set_user` for `user_id`, or the id if there is none
+    fn login(&self, user_id: &UserId) -> String {
+        lock(&self.users)
+            .iter()
+            .find(|(_, id)| *id == user_id)
+            .map_or_else(|| user_id.to_string(), |(login, _)| login.clone())
+    }
+
+    /// The id, login and display name of `user_ids`, like `get_moderators` returns them
+    fn channel_users(&self, user_ids: Option<&Vec<UserId>>) -> Vec<(UserId, String, String)> {
+        user_ids
+            .into_iter()
+            .flatten()
+            .map(|user_id| {
+                let login = self.login(user_id);
+                (user_id.clone(), login.clone(), login)
+            })
+            .collect()
+    }
+}
+
+/// The number of subscriber points a subscription of `tier` is worth
+fn tier_points(tier: &str) -> u64 {
+    match tier {
+        "2000" => 2,
+        "3000" => 6,
+        _ => 1,
+    }
+}
+
+#[async_trait(?Send)]
+impl TwitchApi for MockApiClient {
+    async fn get_broadcaster_id(
+        &self,
+        broadcaster_ident: UserIdent,
+    ) -> Result<UserId, TwitchCtlError> {
+        match broadcaster_ident {
+            UserIdent::None => lock(&self.token_user)
+                .clone()
+                .ok_or(TwitchCtlError::UserTokenRequired),
+            UserIdent::UserId(broadcaster_id) => Ok(broadcaster_id),
+            UserIdent::UserName(login) => lock(&self.users)
+                .get(&login.to_lowercase())
+                .cloned()
+                .ok_or_else(|| {
+                    TwitchCtlError::NotFound(format!("No user with login `{}` found.", login))
+                }),
+        }
+    }
+
+    async fn get_channel_information(&self, id: &UserId) -> Result<ChannelInfo, TwitchCtlError> {
+        lock(&self.channels)
+            .get(id)
+            .cloned()
+            .ok_or_else(|| TwitchCtlError::NotFound(format!("No channel with id `{}` found.", id)))
+    }
+
+    async fn modify_channel_information(
+        &self,
+        id: &UserId,
+        info: ChannelInfo,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::ModifyChannelInformation(id.clone(), info.clone()));
+        let mut channels = lock(&self.channels);
+        let channel = channels.entry(id.clone()).or_default();
+        if info.title.is_some() {
+            channel.title = info.title;
+        }
+        if info.language.is_some() {
+            channel.language = info.language;
+        }
+        if info.category.is_some() {
+            channel.category = info.category;
+            channel.game_name = None;
+        }
+        Ok(())
+    }
+
+    async fn search_categories(
+        &self,
+        term: &str,
+        max: usize,
+        match_mode: CategoryMatch,
+    ) -> Result<Option<Vec<Category>>, TwitchCtlError> {
+        let term_lower = term.to_lowercase();
+        let categories: Vec<_> = lock(&self.categories)
+            .iter()
+            // Twitch also returns categories with similar names, the mock only substrings
+            .filter(|category| {
+                category.name.to_lowercase().contains(&term_lower)
+                    && match_mode.matches(term, &category.name)
+            })
+            .take(max.max(1))
+            .cloned()
+            .collect();
+        if categories.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(categories))
+        }
+    }
+
+    async fn get_stream(&self, user_id: &UserId) -> Result<Option<Stream>, TwitchCtlError> {
+        Ok(lock(&self.streams).get(user_id).cloned())
+    }
+
+    async fn get_stream_tags(&self, id: &UserId) -> Result<Vec<TwitchTag>, TwitchCtlError> {
+        Ok(lock(&self.tags).get(id).cloned().unwrap_or_default())
+    }
+
+    async fn get_rewards(&self, id: &UserId) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        Ok(lock(&self.rewards).get(id).cloned().unwrap_or_default())
+    }
+
+    async fn create_custom_reward(
+        &self,
+        id: &UserId,
+        reward: CreateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::CreateCustomReward(id.clone(), reward.clone()));
+        let number = lock(&self.calls).len();
+        let created = serde_json::from_value(serde_json::json!({
+            "broadcaster_id": id,
+            "broadcaster_login": self.login(id),
+            "broadcaster_name": self.login(id),
+            "id": format!("mock-reward-{}", number),
+            "image": null,
+            "background_color": reward.background_color.unwrap_or_else(|| "#9147FF".into()),
+            "is_enabled": reward.is_enabled.unwrap_or(true),
+            "cost": reward.cost,
+            "title": reward.title,
+            "prompt": reward.prompt.unwrap_or_default(),
+            "is_user_input_required": reward.is_user_input_required.unwrap_or(false),
+            "max_per_stream_setting": {
+                "is_enabled": reward.is_max_per_stream_enabled.unwrap_or(false),
+                "max_per_stream": reward.max_per_stream.unwrap_or(0)
+            },
+            "max_per_user_per_stream_setting": {
+                "is_enabled": reward.is_max_per_user_per_stream_enabled.unwrap_or(false),
+                "max_per_user_per_stream": reward.max_per_user_per_stream.unwrap_or(0)
+            },
+            "global_cooldown_setting": {
+                "is_enabled": reward.is_global_cooldown_enabled.unwrap_or(false),
+                "global_cooldown_seconds": reward.global_cooldown_seconds.unwrap_or(0)
+            },
+            "is_paused": false,
+            "is_in_stock": true,
+            "default_image": {
+                "url_1x": "https://static-cdn.jtvnw.net/custom-reward-images/default-1.png",
+                "url_2x": "https://static-cdn.jtvnw.net/custom-reward-images/default-2.png",
+                "url_4x": "https://static-cdn.jtvnw.net/custom-reward-images/default-4.png"
+            },
+            "should_redemptions_skip_request_queue": reward
+                .should_redemptions_skip_request_queue
+                .unwrap_or(false),
+            "redemptions_redeemed_current_stream": null,
+            "cooldown_expires_at": null
+        }))
+        .map_err(|e| TwitchCtlError::Helix(e.into()))?;
+        lock(&self.rewards)
+            .entry(id.clone())
+            .or_default()
+            .push(created);
+        Ok(())
+    }
+
+    async fn update_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        reward: UpdateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::UpdateCustomReward(
+            broadcaster_id.clone(),
+            reward_id.clone(),
+            reward.clone(),
+        ));
+        let mut rewards = lock(&self.rewards);
+        let stored = rewards
+            .get_mut(broadcaster_id)
+            .into_iter()
+            .flatten()
+            .find(|stored| &stored.id == reward_id)
+            .ok_or_else(|| {
+                TwitchCtlError::NotFound(format!("No reward with id `{}` found.", reward_id))
+            })?;
+        if let Some(title) = reward.title {
+            stored.title = title;
+        }
+        if let Some(prompt) = reward.prompt {
+            stored.prompt = prompt;
+        }
+        if let Some(cost) = reward.cost {
+            stored.cost = cost;
+        }
+        if let Some(background_color) = reward.background_color {
+            stored.background_color = background_color;
+        }
+        if let Some(is_enabled) = reward.is_enabled {
+            stored.is_enabled = is_enabled;
+        }
+        if let Some(is_user_input_required) = reward.is_user_input_required {
+            stored.is_user_input_required = is_user_input_required;
+        }
+        if let Some(is_enabled) = reward.is_max_per_stream_enabled {
+            stored.max_per_stream_setting.is_enabled = is_enabled;
+        }
+        if let Some(max) = reward.max_per_stream {
+            stored.max_per_stream_setting.max_per_stream = max as _;
+        }
+        if let Some(is_enabled) = reward.is_max_per_user_per_stream_enabled {
+            stored.max_per_user_per_stream_setting.is_enabled = is_enabled;
+        }
+        if let Some(max) = reward.max_per_user_per_stream {
+            stored
+                .max_per_user_per_stream_setting
+                .max_per_user_per_stream = max as _;
+        }
+        if let Some(is_enabled) = reward.is_global_cooldown_enabled {
+            stored.global_cooldown_setting.is_enabled = is_enabled;
+        }
+        if let Some(seconds) = reward.global_cooldown_seconds {
+            stored.global_cooldown_setting.global_cooldown_seconds = seconds as _;
+        }
+        if let Some(is_paused) = reward.is_paused {
+            stored.is_paused = is_paused;
+        }
+        if let Some(skip) = reward.should_redemptions_skip_request_queue {
+            stored.should_redemptions_skip_request_queue = skip;
+        }
+        Ok(())
+    }
+
+    async fn delete_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::DeleteCustomReward(
+            broadcaster_id.clone(),
+            reward_id.clone(),
+        ));
+        if let Some(rewards) = lock(&self.rewards).get_mut(broadcaster_id) {
+            rewards.retain(|reward| &reward.id != reward_id);
+        }
+        Ok(())
+    }
+
+    async fn get_redemptions(
+        &self,
+        _broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<Vec<CustomRewardRedemption>, TwitchCtlError> {
+        Ok(lock(&self.redemptions)
+            .get(reward_id)
+            .into_iter()
+            .flatten()
+            .filter(|redemption| redemption.status == status)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_redemption_status(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        redemption_ids: &[RedemptionId],
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::UpdateRedemptionStatus(
+            broadcaster_id.clone(),
+            reward_id.clone(),
+            redemption_ids.to_vec(),
+            status,
+        ));
+        Ok(())
+    }
+
+    async fn replace_stream_tags(
+        &self,
+        broadcaster_id: &UserId,
+        tag_ids: Vec<TagId>,
+    ) -> Result<Vec<TagId>, TwitchCtlError> {
+        self.record(MockCall::ReplaceStreamTags(
+            broadcaster_id.clone(),
+            tag_ids.clone(),
+        ));
+        let tags: Vec<_> = lock(&self.all_tags)
+            .iter()
+            .filter(|tag| tag_ids.contains(&tag.id))
+            .cloned()
+            .collect();
+        lock(&self.tags).insert(broadcaster_id.clone(), tags);
+        Ok(tag_ids)
+    }
+
+    async fn set_stream_tags_by_name(
+        &self,
+        broadcaster_id: &UserId,
+        tag_names: &[String],
+        locale: Option<&str>,
+    ) -> Result<Vec<String>, TwitchCtlError> {
+        let locales = [locale.unwrap_or("en-us"), "en-us"];
+        let (tag_ids, unmatched) = self
+            .resolve_tag_names(tag_names, Some(&locales[..]))
+            .await?;
+        if tag_ids.len() > 5 {
+            return Err(TwitchCtlError::Validation(format!(
+                "A stream can have at most 5 tags, got {}.",
+                tag_ids.len()
+            )));
+        }
+        self.replace_stream_tags(broadcaster_id, tag_ids).await?;
+        Ok(unmatched)
+    }
+
+    async fn get_all_tags(&self) -> Result<Vec<TwitchTag>, TwitchCtlError> {
+        Ok(lock(&self.all_tags).clone())
+    }
+
+    async fn resolve_tag_names(
+        &self,
+        tags: &[String],
+        locales: Option<&[&str]>,
+    ) -> Result<(Vec<TagId>, Vec<String>), TwitchCtlError> {
+        let locales = locales.unwrap_or(&["en-us"]);
+        let all_tags = lock(&self.all_tags);
+        let mut ids = vec![];
+        let mut unmatched = vec![];
+        for tag in tags {
+            match match_tag(&all_tags, tag, locales) {
+                Some(id) => ids.push(id),
+                None => unmatched.push(tag.clone()),
+            }
+        }
+        Ok((ids, unmatched))
+    }
+
+    async fn start_commercial(
+        &self,
+        broadcaster_id: &UserId,
+        length: u32,
+    ) -> Result<Option<Commercial>, TwitchCtlError> {
+        self.record(MockCall::StartCommercial(broadcaster_id.clone(), length));
+        Ok(None)
+    }
+
+    /// The same as `get_rewards`, the mock can manage every reward
+    async fn get_manageable_rewards(
+        &self,
+        id: &UserId,
+    ) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        self.get_rewards(id).await
+    }
+
+    /// Ignores `all`, every follower that was set is returned
+    async fn get_channel_followers(
+        &self,
+        broadcaster_id: &UserId,
+        _all: bool,
+    ) -> Result<ChannelFollowers, TwitchCtlError> {
+        let followers = lock(&self.followers)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default();
+        Ok(ChannelFollowers {
+            total: followers.len() as u64,
+            followers,
+        })
+    }
+
+    async fn get_creator_goals(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<CreatorGoal>, TwitchCtlError> {
+        Ok(lock(&self.goals)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn send_announcement(
+        &self,
+        broadcaster_id: &UserId,
+        message: &str,
+        color: AnnouncementColor,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::SendAnnouncement(
+            broadcaster_id.clone(),
+            message.to_string(),
+            color,
+        ));
+        Ok(())
+    }
+
+    async fn send_shoutout(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let to = self.get_broadcaster_id(to).await?;
+        self.record(MockCall::SendShoutout(from_broadcaster_id.clone(), to));
+        Ok(())
+    }
+
+    async fn get_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChatSettings, TwitchCtlError> {
+        lock(&self.chat_settings)
+            .get(broadcaster_id)
+            .cloned()
+            .ok_or_else(|| {
+                TwitchCtlError::NotFound(format!(
+                    "No chat settings for `{}` found.",
+                    broadcaster_id
+                ))
+            })
+    }
+
+    async fn update_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+        settings: ChatSettingsPatch,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::UpdateChatSettings(
+            broadcaster_id.clone(),
+            settings,
+        ));
+        Ok(())
+    }
+
+    async fn send_whisper(
+        &self,
+        to_user: UserIdent,
+        message: &str,
+        new_conversation: bool,
+    ) -> Result<(), TwitchCtlError> {
+        let to_user = self.get_broadcaster_id(to_user).await?;
+        self.record(MockCall::SendWhisper(
+            to_user,
+            message.to_string(),
+            new_conversation,
+        ));
+        Ok(())
+    }
+
+    async fn create_clip(
+        &self,
+        broadcaster_id: &UserId,
+        has_delay: bool,
+    ) -> Result<Option<CreatedClip>, TwitchCtlError> {
+        self.record(MockCall::CreateClip(broadcaster_id.clone(), has_delay));
+        Ok(None)
+    }
+
+    async fn get_top_games(&self, max: usize) -> Result<Vec<Game>, TwitchCtlError> {
+        Ok(lock(&self.games).iter().take(max).cloned().collect())
+    }
+
+    async fn get_games(
+        &self,
+        ids: &[&CategoryId],
+        names: &[&str],
+    ) -> Result<Vec<Game>, TwitchCtlError> {
+        let games = lock(&self.games);
+        let by_id = games.iter().filter(|game| ids.contains(&&game.id));
+        let by_name = games.iter().filter(|game| {
+            names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&game.name))
+        });
+        Ok(by_id.chain(by_name).cloned().collect())
+    }
+
+    /// Permits every message
+    async fn check_automod(
+        &self,
+        _broadcaster_id: &UserId,
+        messages: Vec<(String, String)>,
+    ) -> Result<Vec<AutoModResult>, TwitchCtlError> {
+        Ok(messages
+            .into_iter()
+            .map(|(msg_id, _)| AutoModResult {
+                msg_id,
+                is_permitted: true,
+            })
+            .collect())
+    }
+
+    async fn add_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        text: &str,
+    ) -> Result<Option<BlockedTerm>, TwitchCtlError> {
+        self.record(MockCall::AddBlockedTerm(
+            broadcaster_id.clone(),
+            text.to_string(),
+        ));
+        Ok(None)
+    }
+
+    async fn get_blocked_terms(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<BlockedTerm>, TwitchCtlError> {
+        Ok(lock(&self.blocked_terms)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn remove_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        term_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::RemoveBlockedTerm(
+            broadcaster_id.clone(),
+            term_id.to_string(),
+        ));
+        if let Some(terms) = lock(&self.blocked_terms).get_mut(broadcaster_id) {
+            terms.retain(|term| term.id != term_id);
+        }
+        Ok(())
+    }
+
+    async fn delete_chat_message(
+        &self,
+        broadcaster_id: &UserId,
+        message_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::DeleteChatMessage(
+            broadcaster_id.clone(),
+            message_id.to_string(),
+        ));
+        Ok(())
+    }
+
+    async fn clear_chat(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::ClearChat(broadcaster_id.clone()));
+        Ok(())
+    }
+
+    async fn get_shield_mode_status(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ShieldModeStatus, TwitchCtlError> {
+        lock(&self.shield_modes)
+            .get(broadcaster_id)
+            .cloned()
+            .ok_or_else(|| {
+                TwitchCtlError::NotFound(format!(
+                    "No shield mode status for `{}` found.",
+                    broadcaster_id
+                ))
+            })
+    }
+
+    async fn set_shield_mode(
+        &self,
+        broadcaster_id: &UserId,
+        active: bool,
+    ) -> Result<Option<ShieldModeStatus>, TwitchCtlError> {
+        self.record(MockCall::SetShieldMode(broadcaster_id.clone(), active));
+        Ok(None)
+    }
+
+    async fn add_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.record(MockCall::AddVip(broadcaster_id.clone(), user_id.clone()));
+        lock(&self.vips)
+            .entry(broadcaster_id.clone())
+            .or_default()
+            .push(user_id);
+        Ok(())
+    }
+
+    async fn remove_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.record(MockCall::RemoveVip(broadcaster_id.clone(), user_id.clone()));
+        if let Some(vips) = lock(&self.vips).get_mut(broadcaster_id) {
+            vips.retain(|vip| vip != &user_id);
+        }
+        Ok(())
+    }
+
+    async fn get_vips(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        Ok(self.channel_users(lock(&self.vips).get(broadcaster_id)))
+    }
+
+    async fn add_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.record(MockCall::AddModerator(
+            broadcaster_id.clone(),
+            user_id.clone(),
+        ));
+        lock(&self.moderators)
+            .entry(broadcaster_id.clone())
+            .or_default()
+            .push(user_id);
+        Ok(())
+    }
+
+    async fn remove_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.record(MockCall::RemoveModerator(
+            broadcaster_id.clone(),
+            user_id.clone(),
+        ));
+        if let Some(moderators) = lock(&self.moderators).get_mut(broadcaster_id) {
+            moderators.retain(|moderator| moderator != &user_id);
+        }
+        Ok(())
+    }
+
+    async fn get_moderators(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        Ok(self.channel_users(lock(&self.moderators).get(broadcaster_id)))
+    }
+
+    async fn create_poll(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        choices: Vec<String>,
+        duration: u32,
+        channel_points_voting: Option<u32>,
+    ) -> Result<Option<Poll>, TwitchCtlError> {
+        self.record(MockCall::CreatePoll(
+            broadcaster_id.clone(),
+            title.to_string(),
+            choices,
+            duration,
+            channel_points_voting,
+        ));
+        Ok(None)
+    }
+
+    async fn get_polls(&self, broadcaster_id: &UserId) -> Result<Vec<Poll>, TwitchCtlError> {
+        Ok(lock(&self.polls)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn end_poll(
+        &self,
+        broadcaster_id: &UserId,
+        poll_id: &str,
+        status: PollEnd,
+    ) -> Result<Option<Poll>, TwitchCtlError> {
+        self.record(MockCall::EndPoll(
+            broadcaster_id.clone(),
+            poll_id.to_string(),
+            status,
+        ));
+        Ok(None)
+    }
+
+    async fn create_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        outcomes: Vec<String>,
+        window: u32,
+    ) -> Result<Option<Prediction>, TwitchCtlError> {
+        self.record(MockCall::CreatePrediction(
+            broadcaster_id.clone(),
+            title.to_string(),
+            outcomes,
+            window,
+        ));
+        Ok(None)
+    }
+
+    async fn get_predictions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<Prediction>, TwitchCtlError> {
+        Ok(lock(&self.predictions)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn end_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        prediction_id: &str,
+        status: PredictionStatus,
+        winning_outcome_id: Option<&str>,
+    ) -> Result<Option<Prediction>, TwitchCtlError> {
+        self.record(MockCall::EndPrediction(
+            broadcaster_id.clone(),
+            prediction_id.to_string(),
+            status,
+            winning_outcome_id.map(str::to_string),
+        ));
+        Ok(None)
+    }
+
+    async fn start_raid(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<Option<Raid>, TwitchCtlError> {
+        let to = self.get_broadcaster_id(to).await?;
+        self.record(MockCall::StartRaid(from_broadcaster_id.clone(), to));
+        Ok(None)
+    }
+
+    async fn cancel_raid(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::CancelRaid(broadcaster_id.clone()));
+        Ok(())
+    }
+
+    async fn get_schedule(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<ScheduleSegment>, TwitchCtlError> {
+        Ok(lock(&self.schedules)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn create_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment: NewScheduleSegment,
+    ) -> Result<Option<ScheduleSegment>, TwitchCtlError> {
+        self.record(MockCall::CreateScheduleSegment(
+            broadcaster_id.clone(),
+            segment,
+        ));
+        Ok(None)
+    }
+
+    async fn update_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+        segment: ScheduleSegmentPatch,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::UpdateScheduleSegment(
+            broadcaster_id.clone(),
+            segment_id.to_string(),
+            segment,
+        ));
+        Ok(())
+    }
+
+    async fn delete_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        self.record(MockCall::DeleteScheduleSegment(
+            broadcaster_id.clone(),
+            segment_id.to_string(),
+        ));
+        if let Some(segments) = lock(&self.schedules).get_mut(broadcaster_id) {
+            segments.retain(|segment| segment.id != segment_id);
+        }
+        Ok(())
+    }
+
+    async fn create_stream_marker(
+        &self,
+        user_id: &UserId,
+        description: Option<String>,
+    ) -> Result<Option<StreamMarker>, TwitchCtlError> {
+        self.record(MockCall::CreateStreamMarker(user_id.clone(), description));
+        Ok(None)
+    }
+
+    /// Returns only the most recent hype train without `history`
+    async fn get_hype_train_events(
+        &self,
+        broadcaster_id: &UserId,
+        history: bool,
+    ) -> Result<Vec<HypeTrain>, TwitchCtlError> {
+        let mut hype_trains = lock(&self.hype_trains)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default();
+        if !history {
+            hype_trains.truncate(1);
+        }
+        Ok(hype_trains)
+    }
+
+    async fn get_subscriptions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChannelSubscriptions, TwitchCtlError> {
+        let subscribers = lock(&self.subscribers)
+            .get(broadcaster_id)
+            .cloned()
+            .unwrap_or_default();
+        Ok(ChannelSubscriptions {
+            total: subscribers.len() as u64,
+            points: subscribers
+                .iter()
+                .map(|subscriber| tier_points(&subscriber.tier))
+                .sum(),
+            subscribers,
+        })
+    }
+
+    async fn block_user(
+        &self,
+        target: UserIdent,
+        source_context: Option<BlockContext>,
+        reason: Option<BlockReason>,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(target).await?;
+        self.record(MockCall::BlockUser(user_id.clone(), source_context, reason));
+        let login = self.login(&user_id);
+        lock(&self.blocked_users).push(BlockedUser {
+            user_id,
+            user_login: login.clone(),
+            display_name: login,
+        });
+        Ok(())
+    }
+
+    async fn unblock_user(&self, target: UserIdent) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(target).await?;
+        self.record(MockCall::UnblockUser(user_id.clone()));
+        lock(&self.blocked_users).retain(|user| user.user_id != user_id);
+        Ok(())
+    }
+
+    async fn get_blocked_users(&self) -> Result<Vec<BlockedUser>, TwitchCtlError> {
+        Ok(lock(&self.blocked_users).clone())
+    }
+
+    async fn get_videos(
+        &self,
+        user_id: &UserId,
+        video_type: Option<VideoType>,
+    ) -> Result<Vec<Video>, TwitchCtlError> {
+        Ok(lock(&self.videos)
+            .iter()
+            .filter(|video| &video.user_id == user_id)
+            .filter(|video| video_type.map_or(true, |video_type| video.video_type == video_type))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_video_by_id(&self, id: &VideoId) -> Result<Option<Video>, TwitchCtlError> {
+        Ok(lock(&self.videos)
+            .iter()
+            .find(|video| &video.id == id)
+            .cloned())
+    }
+
+    async fn delete_videos(&self, video_ids: &[&VideoId]) -> Result<Vec<VideoId>, TwitchCtlError> {
+        self.record(MockCall::DeleteVideos(
+            video_ids.iter().cloned().cloned().collect(),
+        ));
+        let mut videos = lock(&self.videos);
+        let deleted = videos
+            .iter()
+            .map(|video| video.id.clone())
+            .filter(|id| video_ids.contains(&id))
+            .collect();
+        videos.retain(|video| !video_ids.contains(&&video.id));
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,766 @@
+use http::Method;
+use serde::{Deserialize, Deserializer, Serialize};
+use twitch_api2::types::UserId;
+
+use super::{helix::NO_BODY, ApiClient, Operation, UserIdent};
+use crate::error::TwitchCtlError;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ShieldModeStatus {
+    pub is_active: bool,
+    /// the moderator that last changed the shield mode
+    pub moderator_name: String,
+    pub last_activated_at: String,
+}
+
+#[derive(Serialize)]
+struct ShieldModeBody {
+    is_active: bool,
+}
+
+/// A term AutoMod blocks in the chat of a broadcaster
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockedTerm {
+    pub id: String,
+    pub text: String,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BlockedTermBody<'s> {
+    text: &'s str,
+}
+
+/// A user with a role in a channel
+#[derive(Deserialize)]
+struct ChannelUser {
+    user_id: UserId,
+    user_login: String,
+    user_name: String,
+}
+
+/// A channel the token user is a moderator in
+#[derive(Deserialize, Debug, Clone)]
+pub struct ModeratedChannel {
+    pub broadcaster_id: UserId,
+    pub broadcaster_login: String,
+    pub broadcaster_name: String,
+}
+
+/// A user banned or timed out in the chat of a broadcaster
+#[derive(Deserialize, Debug, Clone)]
+pub struct BannedUser {
+    pub user_id: UserId,
+    pub user_login: String,
+    pub user_name: String,
+    /// `None` for permanent bans
+    #[serde(deserialize_with = "none_if_empty")]
+    pub expires_at: Option<String>,
+    pub created_at: String,
+    /// empty if the moderator did not give a reason
+    pub reason: String,
+    pub moderator_id: UserId,
+    pub moderator_login: String,
+    pub moderator_name: String,
+}
+
+/// The longest timeout Twitch allows, two weeks
+pub const MAX_TIMEOUT_SECONDS: u32 = 1_209_600;
+
+#[derive(Serialize)]
+struct BanBody<'s> {
+    data: BanData<'s>,
+}
+
+#[derive(Serialize)]
+struct BanData<'s> {
+    user_id: &'s UserId,
+    /// `None` for permanent bans
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'s str>,
+}
+
+#[derive(Deserialize)]
+struct Ban {
+    end_time: Option<String>,
+}
+
+/// Twitch sends empty strings instead of `null` for some missing values
+fn none_if_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.filter(|value| !value.is_empty()))
+}
+
+/// Whether AutoMod would let a message through
+#[derive(Deserialize, Debug, Clone)]
+pub struct AutoModResult {
+    pub msg_id: String,
+    pub is_permitted: bool,
+}
+
+#[derive(Serialize)]
+struct AutoModCheckBody<'s> {
+    data: Vec<AutoModMessage<'s>>,
+}
+
+#[derive(Serialize)]
+struct AutoModMessage<'s> {
+    msg_id: &'s str,
+    msg_text: &'s str,
+}
+
+/// How strictly AutoMod holds back messages in the chat of a broadcaster.
+///
+/// The levels go from 0, allowing everything, to 4, holding back the most.
+#[derive(Deserialize, Debug, Clone)]
+pub struct AutoModSettings {
+    pub broadcaster_id: UserId,
+    pub moderator_id: UserId,
+    /// `None` if the levels of the categories were set individually
+    pub overall_level: Option<u8>,
+    pub aggression: u8,
+    pub bullying: u8,
+    pub disability: u8,
+    pub misogyny: u8,
+    pub race_ethnicity_or_religion: u8,
+    pub sex_based_terms: u8,
+    pub sexuality_sex_or_gender: u8,
+    pub swearing: u8,
+}
+
+/// Changes to the AutoMod settings, only the fields that are set are changed.
+///
+/// Either the overall level or the levels of categories can be set, not both.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AutoModSettingsPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overall_level: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggression: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bullying: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disability: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub misogyny: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub race_ethnicity_or_religion: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex_based_terms: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sexuality_sex_or_gender: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swearing: Option<u8>,
+}
+
+impl AutoModSettingsPatch {
+    fn categories(&self) -> [Option<u8>; 8] {
+        [
+            self.aggression,
+            self.bullying,
+            self.disability,
+            self.misogyny,
+            self.race_ethnicity_or_religion,
+            self.sex_based_terms,
+            self.sexuality_sex_or_gender,
+            self.swearing,
+        ]
+    }
+
+    fn validate(&self) -> Result<(), TwitchCtlError> {
+        let has_categories = self.categories().iter().any(Option::is_some);
+        if self.overall_level.is_some() && has_categories {
+            return Err(TwitchCtlError::Validation(
+                "Either the overall AutoMod level or the levels of categories can be set, not both."
+                    .to_string(),
+            ));
+        }
+        if self.overall_level.is_none() && !has_categories {
+            return Err(TwitchCtlError::Validation(
+                "No AutoMod level to change was given.".to_string(),
+            ));
+        }
+        let levels = self.categories();
+        let too_high = levels
+            .iter()
+            .chain(std::iter::once(&self.overall_level))
+            .flatten()
+            .find(|level| **level > 4);
+        if let Some(level) = too_high {
+            return Err(TwitchCtlError::Validation(format!(
+                "AutoMod levels go from 0 to 4, got {}.",
+                level
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fills the categories that are not set with the current levels, as Twitch resets them
+    fn keep_unset(mut self, current: &AutoModSettings) -> Self {
+        self.aggression = self.aggression.or(Some(current.aggression));
+        self.bullying = self.bullying.or(Some(current.bullying));
+        self.disability = self.disability.or(Some(current.disability));
+        self.misogyny = self.misogyny.or(Some(current.misogyny));
+        self.race_ethnicity_or_religion = self
+            .race_ethnicity_or_religion
+            .or(Some(current.race_ethnicity_or_religion));
+        self.sex_based_terms = self.sex_based_terms.or(Some(current.sex_based_terms));
+        self.sexuality_sex_or_gender = self
+            .sexuality_sex_or_gender
+            .or(Some(current.sexuality_sex_or_gender));
+        self.swearing = self.swearing.or(Some(current.swearing));
+        self
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns the AutoMod settings of a broadcaster, the token user has to be a moderator.
+    pub async fn get_automod_settings(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<AutoModSettings, TwitchCtlError> {
+        self.helix_data::<Vec<AutoModSettings>, _>(
+            Operation::GetAutoModSettings,
+            Method::GET,
+            "moderation/automod/settings",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?
+        .data
+        .into_iter()
+        .next()
+        .ok_or_else(|| TwitchCtlError::NotFound("Twitch did not return AutoMod settings".into()))
+    }
+
+    /// Changes the AutoMod settings of a broadcaster, the token user has to be a moderator.
+    ///
+    /// Categories that are not set in `settings` keep their level, unless the overall level is
+    /// set, which sets the levels of all categories. Returns the new settings, or `None` in a
+    /// dry run.
+    pub async fn update_automod_settings(
+        &self,
+        broadcaster_id: &UserId,
+        settings: AutoModSettingsPatch,
+    ) -> Result<Option<AutoModSettings>, TwitchCtlError> {
+        settings.validate()?;
+        let settings = match settings.overall_level {
+            Some(_) => settings,
+            None => settings.keep_unset(&self.get_automod_settings(broadcaster_id).await?),
+        };
+        self.helix_write::<Vec<AutoModSettings>, _>(
+            Operation::UpdateAutoModSettings,
+            Method::PUT,
+            "moderation/automod/settings",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            Some(&settings),
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the AutoMod settings".into())
+            })
+        })
+        .transpose()
+    }
+
+    /// Checks whether AutoMod would hold back messages in the chat of a broadcaster.
+    ///
+    /// `messages` are pairs of an id of your choice and the text, the results carry the same ids.
+    pub async fn check_automod(
+        &self,
+        broadcaster_id: &UserId,
+        messages: Vec<(String, String)>,
+    ) -> Result<Vec<AutoModResult>, TwitchCtlError> {
+        let mut results = vec![];
+        // Twitch accepts up to 100 messages per request
+        for messages in messages.chunks(100) {
+            let body = AutoModCheckBody {
+                data: messages
+                    .iter()
+                    .map(|(msg_id, msg_text)| AutoModMessage { msg_id, msg_text })
+                    .collect(),
+            };
+            let mut res = self
+                .helix_data::<Vec<AutoModResult>, _>(
+                    Operation::CheckAutoMod,
+                    Method::POST,
+                    "moderation/enforcements/status",
+                    &[("broadcaster_id", broadcaster_id.to_string())],
+                    Some(&body),
+                )
+                .await?;
+            results.append(&mut res.data);
+        }
+        Ok(results)
+    }
+
+    /// Adds a term of 2 to 500 characters that AutoMod blocks in the chat of a broadcaster.
+    ///
+    /// Returns the added term, or `None` in a dry run.
+    pub async fn add_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        text: &str,
+    ) -> Result<Option<BlockedTerm>, TwitchCtlError> {
+        let length = text.chars().count();
+        if !(2..=500).contains(&length) {
+            return Err(TwitchCtlError::Validation(format!(
+                "Blocked terms have to be 2 to 500 characters long, got {}.",
+                length
+            )));
+        }
+        self.helix_write::<Vec<BlockedTerm>, _>(
+            Operation::AddBlockedTerm,
+            Method::POST,
+            "moderation/blocked_terms",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            Some(&BlockedTermBody { text }),
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the blocked term".into())
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn get_blocked_terms(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<BlockedTerm>, TwitchCtlError> {
+        let terms = self
+            .helix_pages(
+                Operation::GetBlockedTerms,
+                "moderation/blocked_terms",
+                &[
+                    ("broadcaster_id", broadcaster_id.to_string()),
+                    ("moderator_id", self.get_user_id()?.to_string()),
+                ],
+                None,
+            )
+            .await?
+            .data;
+        Ok(terms)
+    }
+
+    pub async fn remove_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        term_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        self.helix_send(
+            Operation::RemoveBlockedTerm,
+            Method::DELETE,
+            "moderation/blocked_terms",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+                ("id", term_id.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Removes a single message from the chat of a broadcaster.
+    ///
+    /// A message that was already removed is not treated as an error.
+    pub async fn delete_chat_message(
+        &self,
+        broadcaster_id: &UserId,
+        message_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        self.delete_chat_messages(broadcaster_id, Some(message_id))
+            .await
+    }
+
+    /// Removes all messages from the chat of a broadcaster.
+    pub async fn clear_chat(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError> {
+        self.delete_chat_messages(broadcaster_id, None).await
+    }
+
+    async fn delete_chat_messages(
+        &self,
+        broadcaster_id: &UserId,
+        message_id: Option<&str>,
+    ) -> Result<(), TwitchCtlError> {
+        self.confirmed(&match message_id {
+            Some(message_id) => format!("Delete the chat message {}?", message_id),
+            None => format!("Clear the chat of {}?", broadcaster_id),
+        })?;
+        let mut query = vec![
+            ("broadcaster_id", broadcaster_id.to_string()),
+            ("moderator_id", self.get_user_id()?.to_string()),
+        ];
+        if let Some(message_id) = message_id {
+            query.push(("message_id", message_id.to_string()));
+        }
+        match self
+            .helix_send(
+                Operation::DeleteChatMessages,
+                Method::DELETE,
+                "moderation/chat",
+                &query,
+                NO_BODY,
+            )
+            .await
+        {
+            Ok(_) | Err(TwitchCtlError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn get_shield_mode_status(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ShieldModeStatus, TwitchCtlError> {
+        self.shield_mode(
+            Operation::GetShieldModeStatus,
+            Method::GET,
+            broadcaster_id,
+            NO_BODY,
+        )
+        .await?
+        .ok_or(TwitchCtlError::DryRun)
+    }
+
+    /// Activates or deactivates shield mode as the token user.
+    ///
+    /// Returns the new status, or `None` in a dry run.
+    pub async fn set_shield_mode(
+        &self,
+        broadcaster_id: &UserId,
+        active: bool,
+    ) -> Result<Option<ShieldModeStatus>, TwitchCtlError> {
+        self.shield_mode(
+            Operation::UpdateShieldModeStatus,
+            Method::PUT,
+            broadcaster_id,
+            Some(&ShieldModeBody { is_active: active }),
+        )
+        .await
+    }
+
+    async fn shield_mode<B: Serialize>(
+        &self,
+        op: Operation,
+        method: Method,
+        broadcaster_id: &UserId,
+        body: Option<&B>,
+    ) -> Result<Option<ShieldModeStatus>, TwitchCtlError> {
+        self.helix_write::<Vec<ShieldModeStatus>, _>(
+            op,
+            method,
+            "moderation/shield_mode",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            body,
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the shield mode status".into())
+            })
+        })
+        .transpose()
+    }
+
+    /// Makes a user a VIP in the channel of a broadcaster.
+    ///
+    /// Fails with [`TwitchCtlError::VipLimitReached`] when all VIP slots are taken.
+    pub async fn add_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.helix_send(
+            Operation::AddVip,
+            Method::POST,
+            "channels/vips",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("user_id", user_id.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await
+        .map_err(|e| {
+            if matches!(e.helix_response(), Some(res) if res.status == 409) {
+                TwitchCtlError::VipLimitReached
+            } else {
+                e
+            }
+        })?;
+        Ok(())
+    }
+
+    pub async fn remove_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.helix_send(
+            Operation::RemoveVip,
+            Method::DELETE,
+            "channels/vips",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("user_id", user_id.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Bans a user from the chat of a broadcaster permanently, use
+    /// [`ApiClient::timeout_user`] for bans that end.
+    pub async fn ban_user(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+        reason: Option<&str>,
+    ) -> Result<(), TwitchCtlError> {
+        self.ban(broadcaster_id, user, None, reason).await?;
+        Ok(())
+    }
+
+    /// Bans a user from the chat of a broadcaster for 1 to [`MAX_TIMEOUT_SECONDS`] seconds and
+    /// returns when the timeout ends, or `None` in a dry run.
+    pub async fn timeout_user(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+        seconds: u32,
+        reason: Option<&str>,
+    ) -> Result<Option<String>, TwitchCtlError> {
+        if !(1..=MAX_TIMEOUT_SECONDS).contains(&seconds) {
+            return Err(TwitchCtlError::Validation(format!(
+                "Timeouts have to be 1 to {} seconds long, got {}.",
+                MAX_TIMEOUT_SECONDS, seconds
+            )));
+        }
+        match self
+            .ban(broadcaster_id, user, Some(seconds), reason)
+            .await?
+        {
+            Some(ban) => ban.end_time.map(Some).ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the end of the timeout".into())
+            }),
+            None => Ok(None),
+        }
+    }
+
+    async fn ban(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+        duration: Option<u32>,
+        reason: Option<&str>,
+    ) -> Result<Option<Ban>, TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        let body = BanBody {
+            data: BanData {
+                user_id: &user_id,
+                duration,
+                reason,
+            },
+        };
+        self.helix_write::<Vec<Ban>, _>(
+            Operation::BanUsers,
+            Method::POST,
+            "moderation/bans",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("moderator_id", self.get_user_id()?.to_string()),
+            ],
+            Some(&body),
+        )
+        .await?
+        .map(|bans| {
+            bans.data
+                .into_iter()
+                .next()
+                .ok_or_else(|| TwitchCtlError::Helix("Twitch did not return the ban".into()))
+        })
+        .transpose()
+    }
+
+    /// Returns the users banned or timed out in the chat of a broadcaster.
+    ///
+    /// With `user_ids` only those users are returned if they are banned, up to 100 can be given.
+    pub async fn get_banned_users(
+        &self,
+        broadcaster_id: &UserId,
+        user_ids: &[&UserId],
+    ) -> Result<Vec<BannedUser>, TwitchCtlError> {
+        if user_ids.len() > 100 {
+            return Err(TwitchCtlError::Validation(format!(
+                "At most 100 users can be checked at once, got {}.",
+                user_ids.len()
+            )));
+        }
+        let mut query = vec![("broadcaster_id", broadcaster_id.to_string())];
+        query.extend(user_ids.iter().map(|id| ("user_id", id.to_string())));
+        Ok(self
+            .helix_pages(Operation::GetBannedUsers, "moderation/banned", &query, None)
+            .await?
+            .data)
+    }
+
+    /// Returns the id, login and display name of every VIP of a broadcaster.
+    pub async fn get_vips(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        self.channel_users(Operation::GetVips, "channels/vips", broadcaster_id)
+            .await
+    }
+
+    /// Makes a user a moderator in the channel of a broadcaster.
+    ///
+    /// Fails with [`TwitchCtlError::AlreadyModerator`] if they already are one.
+    pub async fn add_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.moderator(
+            Operation::AddModerator,
+            Method::POST,
+            broadcaster_id,
+            &user_id,
+        )
+        .await
+        .map_err(|e| {
+            if bad_request(&e, "already") {
+                TwitchCtlError::AlreadyModerator(user_id.to_string())
+            } else {
+                e
+            }
+        })
+    }
+
+    /// Removes the moderator status of a user.
+    ///
+    /// Fails with [`TwitchCtlError::NotModerator`] if they are not a moderator.
+    pub async fn remove_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        let user_id = self.get_broadcaster_id(user).await?;
+        self.moderator(
+            Operation::RemoveModerator,
+            Method::DELETE,
+            broadcaster_id,
+            &user_id,
+        )
+        .await
+        .map_err(|e| {
+            if bad_request(&e, "not a moderator") {
+                TwitchCtlError::NotModerator(user_id.to_string())
+            } else {
+                e
+            }
+        })
+    }
+
+    /// Returns the channels the token user is a moderator in.
+    ///
+    /// The token needs the `user:read:moderated_channels` scope.
+    pub async fn get_moderated_channels(&self) -> Result<Vec<ModeratedChannel>, TwitchCtlError> {
+        Ok(self
+            .helix_pages(
+                Operation::GetModeratedChannels,
+                "moderation/channels",
+                &[("user_id", self.get_user_id()?.to_string())],
+                None,
+            )
+            .await?
+            .data)
+    }
+
+    /// Returns the id, login and display name of every moderator of a broadcaster.
+    pub async fn get_moderators(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        self.channel_users(
+            Operation::GetModerators,
+            "moderation/moderators",
+            broadcaster_id,
+        )
+        .await
+    }
+
+    async fn moderator(
+        &self,
+        op: Operation,
+        method: Method,
+        broadcaster_id: &UserId,
+        user_id: &UserId,
+    ) -> Result<(), TwitchCtlError> {
+        self.helix_send(
+            op,
+            method,
+            "moderation/moderators",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("user_id", user_id.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn channel_users(
+        &self,
+        op: Operation,
+        endpoint: &str,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        let users = self
+            .helix_pages::<ChannelUser>(
+                op,
+                endpoint,
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                None,
+            )
+            .await?
+            .data;
+        Ok(users
+            .into_iter()
+            .map(|user| (user.user_id, user.user_login, user.user_name))
+            .collect())
+    }
+}
+
+/// Whether Twitch rejected the request with a message containing `reason`
+fn bad_request(error: &TwitchCtlError, reason: &str) -> bool {
+    matches!(
+        error.helix_response(),
+        Some(res) if res.status == 400 && res.message.contains(reason)
+    )
+}
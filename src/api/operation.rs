@@ -0,0 +1,259 @@
+use std::borrow::Cow;
+use twitch_api2::twitch_oauth2::Scope;
+
+use super::ApiClient;
+
+/// The operations of [`ApiClient`], every request is sent for one of them
+///
+/// Operations creating something, marked as not idempotent, can have an effect twice when they
+/// are sent again, so their requests are not retried after timeouts, and only retried when rate
+/// limited if the [`RetryPolicy`](super::RetryPolicy) allows it. See
+/// [`Operation::is_idempotent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    SearchCategories,
+    SearchChannels,
+    GetUsers,
+    GetStreamTags,
+    ReplaceStreamTags,
+    GetChannelInformation,
+    ModifyChannelInformation,
+    GetChannelFollowers,
+    GetCreatorGoals,
+    GetStreams,
+    GetGames,
+    GetVideos,
+    GetClips,
+    GetEmotes,
+    GetCheermotes,
+    /// not idempotent
+    StartCommercial,
+    /// not idempotent
+    CreateStreamMarker,
+    /// not idempotent
+    CreateClip,
+    GetRewards,
+    /// not idempotent
+    CreateRewards,
+    /// updating and deleting rewards
+    ManageRewards,
+    GetRedemptions,
+    UpdateRedemptionStatus,
+    GetSubscriptions,
+    GetHypeTrainEvents,
+    GetPolls,
+    /// not idempotent
+    CreatePoll,
+    EndPoll,
+    GetPredictions,
+    /// not idempotent
+    CreatePrediction,
+    EndPrediction,
+    /// not idempotent
+    StartRaid,
+    CancelRaid,
+    GetSchedule,
+    /// not idempotent
+    CreateScheduleSegment,
+    /// updating and deleting schedule segments
+    ManageSchedule,
+    /// not idempotent
+    SendAnnouncement,
+    /// not idempotent
+    SendShoutout,
+    /// not idempotent
+    SendWhisper,
+    GetChatSettings,
+    UpdateChatSettings,
+    /// deleting single messages and clearing the chat
+    DeleteChatMessages,
+    GetModerators,
+    /// not idempotent
+    AddModerator,
+    RemoveModerator,
+    GetModeratedChannels,
+    GetVips,
+    /// not idempotent
+    AddVip,
+    RemoveVip,
+    GetBannedUsers,
+    /// banning users and timing them out, not idempotent
+    BanUsers,
+    GetAutoModSettings,
+    UpdateAutoModSettings,
+    /// checking whether AutoMod would hold back messages
+    CheckAutoMod,
+    GetBlockedTerms,
+    /// not idempotent
+    AddBlockedTerm,
+    RemoveBlockedTerm,
+    GetShieldModeStatus,
+    UpdateShieldModeStatus,
+    DeleteVideos,
+    GetBlockedUsers,
+    /// blocking and unblocking users
+    ManageBlockedUsers,
+    /// the active extensions of a user, or of the token user without scopes
+    GetUserExtensions,
+    /// activating, deactivating and moving the extensions of the token user
+    ManageExtensions,
+    /// subscribing to EventSub events, the scopes depend on the subscription type, not idempotent
+    CreateEventSubSubscription,
+}
+
+/// A scope twitch_oauth2 does not have a variant for yet
+const fn other(scope: &'static str) -> Scope {
+    Scope::Other(Cow::Borrowed(scope))
+}
+
+const MODERATOR_READ_FOLLOWERS: &[Scope] = &[other("moderator:read:followers")];
+const CHANNEL_READ_GOALS: &[Scope] = &[other("channel:read:goals")];
+const CHANNEL_MANAGE_RAIDS: &[Scope] = &[other("channel:manage:raids")];
+const MODERATOR_MANAGE_ANNOUNCEMENTS: &[Scope] = &[other("moderator:manage:announcements")];
+const MODERATOR_MANAGE_SHOUTOUTS: &[Scope] = &[other("moderator:manage:shoutouts")];
+const USER_MANAGE_WHISPERS: &[Scope] = &[other("user:manage:whispers")];
+const MODERATOR_MANAGE_CHAT_SETTINGS: &[Scope] = &[other("moderator:manage:chat_settings")];
+const MODERATOR_MANAGE_CHAT_MESSAGES: &[Scope] = &[other("moderator:manage:chat_messages")];
+const CHANNEL_MANAGE_MODERATORS: &[Scope] = &[other("channel:manage:moderators")];
+const USER_READ_MODERATED_CHANNELS: &[Scope] = &[other("user:read:moderated_channels")];
+const CHANNEL_READ_VIPS: &[Scope] = &[other("channel:read:vips")];
+const CHANNEL_MANAGE_VIPS: &[Scope] = &[other("channel:manage:vips")];
+const MODERATOR_MANAGE_BANNED_USERS: &[Scope] = &[other("moderator:manage:banned_users")];
+const MODERATOR_READ_AUTOMOD_SETTINGS: &[Scope] = &[other("moderator:read:automod_settings")];
+const MODERATOR_MANAGE_AUTOMOD_SETTINGS: &[Scope] = &[other("moderator:manage:automod_settings")];
+const MODERATOR_READ_BLOCKED_TERMS: &[Scope] = &[other("moderator:read:blocked_terms")];
+const MODERATOR_MANAGE_BLOCKED_TERMS: &[Scope] = &[other("moderator:manage:blocked_terms")];
+const MODERATOR_READ_SHIELD_MODE: &[Scope] = &[other("moderator:read:shield_mode")];
+const MODERATOR_MANAGE_SHIELD_MODE: &[Scope] = &[other("moderator:manage:shield_mode")];
+
+impl Operation {
+    /// The scopes the token needs for this operation
+    pub fn required_scopes(&self) -> &'static [Scope] {
+        match self {
+            Operation::SearchCategories
+            | Operation::SearchChannels
+            | Operation::GetUsers
+            | Operation::GetStreamTags
+            | Operation::GetChannelInformation
+            | Operation::GetStreams
+            | Operation::GetGames
+            | Operation::GetVideos
+            | Operation::GetClips
+            | Operation::GetEmotes
+            | Operation::GetCheermotes
+            | Operation::GetSchedule
+            | Operation::GetChatSettings
+            | Operation::GetUserExtensions => &[],
+            Operation::ReplaceStreamTags
+            | Operation::ModifyChannelInformation
+            | Operation::CreateStreamMarker => &[Scope::ChannelManageBroadcast],
+            Operation::GetChannelFollowers => MODERATOR_READ_FOLLOWERS,
+            Operation::GetCreatorGoals => CHANNEL_READ_GOALS,
+            Operation::StartCommercial => &[Scope::ChannelEditCommercial],
+            Operation::CreateClip => &[Scope::ClipsEdit],
+            Operation::GetRewards | Operation::GetRedemptions => &[Scope::ChannelReadRedemptions],
+            Operation::CreateRewards
+            | Operation::ManageRewards
+            | Operation::UpdateRedemptionStatus => &[Scope::ChannelManageRedemptions],
+            Operation::GetSubscriptions => &[Scope::ChannelReadSubscriptions],
+            Operation::GetHypeTrainEvents => &[Scope::ChannelReadHypeTrain],
+            Operation::GetPolls => &[Scope::ChannelReadPolls],
+            Operation::CreatePoll | Operation::EndPoll => &[Scope::ChannelManagePolls],
+            Operation::GetPredictions => &[Scope::ChannelReadPredictions],
+            Operation::CreatePrediction | Operation::EndPrediction => {
+                &[Scope::ChannelManagePredictions]
+            }
+            Operation::StartRaid | Operation::CancelRaid => CHANNEL_MANAGE_RAIDS,
+            Operation::CreateScheduleSegment | Operation::ManageSchedule => {
+                &[Scope::ChannelManageSchedule]
+            }
+            Operation::SendAnnouncement => MODERATOR_MANAGE_ANNOUNCEMENTS,
+            Operation::SendShoutout => MODERATOR_MANAGE_SHOUTOUTS,
+            Operation::SendWhisper => USER_MANAGE_WHISPERS,
+            Operation::UpdateChatSettings => MODERATOR_MANAGE_CHAT_SETTINGS,
+            Operation::DeleteChatMessages => MODERATOR_MANAGE_CHAT_MESSAGES,
+            Operation::GetModerators | Operation::GetBannedUsers | Operation::CheckAutoMod => {
+                &[Scope::ModerationRead]
+            }
+            Operation::AddModerator | Operation::RemoveModerator => CHANNEL_MANAGE_MODERATORS,
+            Operation::GetModeratedChannels => USER_READ_MODERATED_CHANNELS,
+            Operation::GetVips => CHANNEL_READ_VIPS,
+            Operation::AddVip | Operation::RemoveVip => CHANNEL_MANAGE_VIPS,
+            Operation::BanUsers => MODERATOR_MANAGE_BANNED_USERS,
+            Operation::GetAutoModSettings => MODERATOR_READ_AUTOMOD_SETTINGS,
+            Operation::UpdateAutoModSettings => MODERATOR_MANAGE_AUTOMOD_SETTINGS,
+            Operation::GetBlockedTerms => MODERATOR_READ_BLOCKED_TERMS,
+            Operation::AddBlockedTerm | Operation::RemoveBlockedTerm => {
+                MODERATOR_MANAGE_BLOCKED_TERMS
+            }
+            Operation::GetShieldModeStatus => MODERATOR_READ_SHIELD_MODE,
+            Operation::UpdateShieldModeStatus => MODERATOR_MANAGE_SHIELD_MODE,
+            Operation::DeleteVideos => &[Scope::ChannelManageVideos],
+            Operation::GetBlockedUsers => &[Scope::UserReadBlockedUsers],
+            Operation::ManageBlockedUsers => &[Scope::UserManageBlockedUsers],
+            Operation::ManageExtensions => &[Scope::UserEditBroadcast],
+            Operation::CreateEventSubSubscription => &[],
+        }
+    }
+
+    /// Whether sending the operation twice has the same effect as sending it once.
+    ///
+    /// Reads, replacing, updating and deleting are idempotent, creating is not.
+    pub fn is_idempotent(&self) -> bool {
+        !matches!(
+            self,
+            Operation::StartCommercial
+                | Operation::CreateStreamMarker
+                | Operation::CreateClip
+                | Operation::CreateRewards
+                | Operation::CreatePoll
+                | Operation::CreatePrediction
+                | Operation::StartRaid
+                | Operation::CreateScheduleSegment
+                | Operation::SendAnnouncement
+                | Operation::SendShoutout
+                | Operation::SendWhisper
+                | Operation::AddModerator
+                | Operation::AddVip
+                | Operation::BanUsers
+                | Operation::AddBlockedTerm
+                | Operation::CreateEventSubSubscription
+        )
+    }
+
+    /// Whether the operation acts on behalf of a user, so app access tokens can not perform it
+    pub fn requires_user_token(&self) -> bool {
+        !matches!(
+            self,
+            Operation::SearchCategories
+                | Operation::SearchChannels
+                | Operation::GetUsers
+                | Operation::GetStreamTags
+                | Operation::GetChannelInformation
+                | Operation::GetStreams
+                | Operation::GetGames
+                | Operation::GetVideos
+                | Operation::GetClips
+                | Operation::GetEmotes
+                | Operation::GetCheermotes
+                | Operation::GetSchedule
+                | Operation::GetChatSettings
+                | Operation::GetUserExtensions
+        )
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Whether the token is allowed to perform `op`.
+    pub fn can(&self, op: Operation) -> bool {
+        if op.requires_user_token() && self.user.is_none() {
+            return false;
+        }
+        op.required_scopes().iter().all(|scope| {
+            self.scopes.contains(scope)
+                // managing redemptions includes reading them
+                || (scope == &Scope::ChannelReadRedemptions
+                    && self.scopes.contains(&Scope::ChannelManageRedemptions))
+        })
+    }
+}
@@ -0,0 +1,159 @@
+use http::Method;
+use serde::{Deserialize, Serialize};
+use twitch_api2::types::UserId;
+
+use super::{ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Poll {
+    pub id: String,
+    pub title: String,
+    pub choices: Vec<PollChoice>,
+    /// `ACTIVE`, `COMPLETED`, `TERMINATED`, `ARCHIVED`, `MODERATED` or `INVALID`
+    pub status: String,
+    /// the duration in seconds
+    pub duration: u32,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PollChoice {
+    pub id: String,
+    pub title: String,
+    pub votes: u64,
+    pub channel_points_votes: u64,
+}
+
+/// How to end a poll
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PollEnd {
+    /// end the poll, the results stay visible
+    Terminated,
+    /// end the poll and hide the results
+    Archived,
+}
+
+#[derive(Serialize)]
+struct CreatePollBody<'s> {
+    broadcaster_id: &'s UserId,
+    title: &'s str,
+    choices: Vec<PollChoiceBody>,
+    duration: u32,
+    channel_points_voting_enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel_points_per_vote: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct PollChoiceBody {
+    title: String,
+}
+
+#[derive(Serialize)]
+struct EndPollBody<'s> {
+    broadcaster_id: &'s UserId,
+    id: &'s str,
+    status: &'static str,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Starts a poll with 2 to 5 choices, running for 15 to 1800 seconds.
+    ///
+    /// With `channel_points_voting` viewers can buy extra votes for that many channel points.
+    /// Returns the started poll, or `None` in a dry run.
+    pub async fn create_poll(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        choices: Vec<String>,
+        duration: u32,
+        channel_points_voting: Option<u32>,
+    ) -> Result<Option<Poll>, TwitchCtlError> {
+        if !(2..=5).contains(&choices.len()) {
+            return Err(TwitchCtlError::Validation(format!(
+                "A poll needs 2 to 5 choices, got {}.",
+                choices.len()
+            )));
+        }
+        if !(15..=1800).contains(&duration) {
+            return Err(TwitchCtlError::Validation(format!(
+                "A poll has to run for 15 to 1800 seconds, got {}.",
+                duration
+            )));
+        }
+        let body = CreatePollBody {
+            broadcaster_id,
+            title,
+            choices: choices
+                .into_iter()
+                .map(|title| PollChoiceBody { title })
+                .collect(),
+            duration,
+            channel_points_voting_enabled: channel_points_voting.is_some(),
+            channel_points_per_vote: channel_points_voting,
+        };
+        self.helix_write::<Vec<Poll>, _>(
+            Operation::CreatePoll,
+            Method::POST,
+            "polls",
+            &[],
+            Some(&body),
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the created poll".into())
+            })
+        })
+        .transpose()
+    }
+
+    /// Returns the polls of a broadcaster, most recent first.
+    pub async fn get_polls(&self, broadcaster_id: &UserId) -> Result<Vec<Poll>, TwitchCtlError> {
+        let polls = self
+            // Twitch returns at most 20 polls per page
+            .helix_pages_sized(
+                Operation::GetPolls,
+                "polls",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                None,
+                20,
+            )
+            .await?
+            .data;
+        Ok(polls)
+    }
+
+    /// Ends an active poll and returns it, or `None` in a dry run.
+    pub async fn end_poll(
+        &self,
+        broadcaster_id: &UserId,
+        poll_id: &str,
+        status: PollEnd,
+    ) -> Result<Option<Poll>, TwitchCtlError> {
+        let body = EndPollBody {
+            broadcaster_id,
+            id: poll_id,
+            status: match status {
+                PollEnd::Terminated => "TERMINATED",
+                PollEnd::Archived => "ARCHIVED",
+            },
+        };
+        self.helix_write::<Vec<Poll>, _>(
+            Operation::EndPoll,
+            Method::PATCH,
+            "polls",
+            &[],
+            Some(&body),
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::NotFound(format!("No poll with id `{}` found.", poll_id))
+            })
+        })
+        .transpose()
+    }
+}
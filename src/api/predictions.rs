@@ -0,0 +1,183 @@
+use http::Method;
+use serde::{Deserialize, Serialize};
+use twitch_api2::types::UserId;
+
+use super::{ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Prediction {
+    pub id: String,
+    pub title: String,
+    pub winning_outcome_id: Option<String>,
+    pub outcomes: Vec<PredictionOutcome>,
+    /// how long viewers can make predictions, in seconds
+    pub prediction_window: u32,
+    pub status: PredictionStatus,
+    pub created_at: String,
+    pub ended_at: Option<String>,
+    pub locked_at: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PredictionOutcome {
+    pub id: String,
+    pub title: String,
+    /// the number of users that predicted this outcome
+    pub users: Option<u64>,
+    pub channel_points: Option<u64>,
+    /// `BLUE` or `PINK`
+    pub color: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PredictionStatus {
+    /// viewers can still make predictions
+    Active,
+    /// the winning outcome was chosen and the points were paid out
+    Resolved,
+    /// the prediction was canceled and the points were refunded
+    Canceled,
+    /// viewers can no longer make predictions
+    Locked,
+}
+
+#[derive(Serialize)]
+struct CreatePredictionBody<'s> {
+    broadcaster_id: &'s UserId,
+    title: &'s str,
+    outcomes: Vec<OutcomeBody>,
+    prediction_window: u32,
+}
+
+#[derive(Serialize)]
+struct OutcomeBody {
+    title: String,
+}
+
+#[derive(Serialize)]
+struct EndPredictionBody<'s> {
+    broadcaster_id: &'s UserId,
+    id: &'s str,
+    status: PredictionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    winning_outcome_id: Option<&'s str>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Starts a prediction with two outcomes, viewers can predict for 30 to 1800 seconds.
+    ///
+    /// Returns the started prediction, or `None` in a dry run.
+    pub async fn create_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        outcomes: Vec<String>,
+        window: u32,
+    ) -> Result<Option<Prediction>, TwitchCtlError> {
+        if outcomes.len() != 2 {
+            return Err(TwitchCtlError::Validation(format!(
+                "A prediction needs exactly 2 outcomes, got {}.",
+                outcomes.len()
+            )));
+        }
+        if !(30..=1800).contains(&window) {
+            return Err(TwitchCtlError::Validation(format!(
+                "A prediction window has to be 30 to 1800 seconds, got {}.",
+                window
+            )));
+        }
+        let body = CreatePredictionBody {
+            broadcaster_id,
+            title,
+            outcomes: outcomes
+                .into_iter()
+                .map(|title| OutcomeBody { title })
+                .collect(),
+            prediction_window: window,
+        };
+        self.helix_write::<Vec<Prediction>, _>(
+            Operation::CreatePrediction,
+            Method::POST,
+            "predictions",
+            &[],
+            Some(&body),
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the created prediction".into())
+            })
+        })
+        .transpose()
+    }
+
+    /// Returns the predictions of a broadcaster, most recent first.
+    pub async fn get_predictions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<Prediction>, TwitchCtlError> {
+        let predictions = self
+            // Twitch returns at most 25 predictions per page
+            .helix_pages_sized(
+                Operation::GetPredictions,
+                "predictions",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                None,
+                25,
+            )
+            .await?
+            .data;
+        Ok(predictions)
+    }
+
+    /// Locks, resolves or cancels a prediction.
+    ///
+    /// Resolving requires the id of the winning outcome. Returns the ended prediction, or `None`
+    /// in a dry run.
+    pub async fn end_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        prediction_id: &str,
+        status: PredictionStatus,
+        winning_outcome_id: Option<&str>,
+    ) -> Result<Option<Prediction>, TwitchCtlError> {
+        match (status, winning_outcome_id) {
+            (PredictionStatus::Active, _) => {
+                return Err(TwitchCtlError::Validation(
+                    "A prediction can not be ended as active.".to_string(),
+                ))
+            }
+            (PredictionStatus::Resolved, None) => {
+                return Err(TwitchCtlError::Validation(
+                    "Resolving a prediction requires the winning outcome.".to_string(),
+                ))
+            }
+            _ => {}
+        }
+        let body = EndPredictionBody {
+            broadcaster_id,
+            id: prediction_id,
+            status,
+            winning_outcome_id: winning_outcome_id.filter(|_| status == PredictionStatus::Resolved),
+        };
+        self.helix_write::<Vec<Prediction>, _>(
+            Operation::EndPrediction,
+            Method::PATCH,
+            "predictions",
+            &[],
+            Some(&body),
+        )
+        .await?
+        .map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::NotFound(format!(
+                    "No prediction with id `{}` found.",
+                    prediction_id
+                ))
+            })
+        })
+        .transpose()
+    }
+}
@@ -0,0 +1,68 @@
+use http::Method;
+use serde::Deserialize;
+use twitch_api2::types::UserId;
+
+use super::{helix::NO_BODY, ApiClient, Operation, UserIdent};
+use crate::error::TwitchCtlError;
+
+/// A raid that was started and is counting down
+#[derive(Deserialize, Debug, Clone)]
+pub struct Raid {
+    pub created_at: String,
+    /// whether the raided channel is for mature audiences
+    pub is_mature: bool,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Starts a raid from one broadcaster to another.
+    ///
+    /// Raiding the own channel fails with [`TwitchCtlError::RaidSelf`],
+    /// raiding a channel that is not live with [`TwitchCtlError::RaidTargetOffline`].
+    /// Returns `None` in a dry run.
+    pub async fn start_raid(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<Option<Raid>, TwitchCtlError> {
+        let to_broadcaster_id = self.get_broadcaster_id(to).await?;
+        if &to_broadcaster_id == from_broadcaster_id {
+            return Err(TwitchCtlError::RaidSelf);
+        }
+        if self.get_stream(&to_broadcaster_id).await?.is_none() {
+            return Err(TwitchCtlError::RaidTargetOffline(
+                to_broadcaster_id.to_string(),
+            ));
+        }
+        self.helix_write::<Vec<Raid>, _>(
+            Operation::StartRaid,
+            Method::POST,
+            "raids",
+            &[
+                ("from_broadcaster_id", from_broadcaster_id.to_string()),
+                ("to_broadcaster_id", to_broadcaster_id.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?
+        .map(|res| {
+            res.data
+                .into_iter()
+                .next()
+                .ok_or_else(|| TwitchCtlError::Helix("Twitch did not confirm the raid".into()))
+        })
+        .transpose()
+    }
+
+    /// Cancels a pending raid.
+    pub async fn cancel_raid(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError> {
+        self.helix_send(
+            Operation::CancelRaid,
+            Method::DELETE,
+            "raids",
+            &[("broadcaster_id", broadcaster_id.to_string())],
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+}
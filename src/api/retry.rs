@@ -0,0 +1,212 @@
+use http::HeaderMap;
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::Semaphore;
+
+use super::{ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+tokio::task_local! {
+    static RETRY_ANYWAY: bool;
+}
+
+/// How requests are retried when Twitch's rate limit is exceeded
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry requests
+    pub fn disabled() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// The delay before the first retry, it doubles with every further retry
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Also retry rate limited requests that could have side effects when sent twice, e.g.
+    /// creating a reward.
+    ///
+    /// Twitch does not process rate limited requests, so this is safe unless something else
+    /// sends the same request. Timed out requests could have been processed and are never
+    /// retried when not idempotent, see [`ApiClient::retry_anyway`] for that.
+    pub fn retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = self.base_delay * 2u32.saturating_pow(attempt);
+        retry_after.map_or(backoff, |retry_after| retry_after.max(backoff))
+    }
+}
+
+/// The rate limit budget of the client, as of the last Helix response
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitStatus {
+    /// the requests allowed per window
+    pub limit: Option<u32>,
+    /// the requests left in the current window
+    pub remaining: u32,
+    /// when the window resets and all requests are available again
+    pub reset: SystemTime,
+}
+
+/// Remembers the rate limit from the headers of the Helix responses
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RateLimitTracker {
+    status: Arc<Mutex<Option<RateLimitStatus>>>,
+}
+
+impl RateLimitTracker {
+    pub(crate) fn status(&self) -> Option<RateLimitStatus> {
+        *self.status.lock().expect("lock is not poisoned")
+    }
+
+    /// The time until the rate limit resets
+    pub(crate) fn retry_after(&self) -> Option<Duration> {
+        let reset = self.status()?.reset;
+        Some(
+            reset
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::from_secs(0)),
+        )
+    }
+
+    /// Remembers the rate limit from the `headers` of a response
+    pub(crate) fn track(&self, headers: &HeaderMap) {
+        super::trace::rate_limit(headers);
+        let remaining = header(headers, "Ratelimit-Remaining");
+        let reset = header::<u64>(headers, "Ratelimit-Reset");
+        if let (Some(remaining), Some(reset)) = (remaining, reset) {
+            *self.status.lock().expect("lock is not poisoned") = Some(RateLimitStatus {
+                limit: header(headers, "Ratelimit-Limit"),
+                remaining,
+                reset: UNIX_EPOCH + Duration::from_secs(reset),
+            });
+        }
+    }
+}
+
+/// The last value of the header `name`
+pub(crate) fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers
+        .get_all(name)
+        .iter()
+        .last()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+impl<'a> ApiClient<'a> {
+    /// The rate limit budget as of the last response, `None` before the first request.
+    ///
+    /// This can be used to slow down bulk operations before Twitch starts rejecting requests.
+    pub fn rate_limit_status(&self) -> Option<RateLimitStatus> {
+        self.rate_limit.status()
+    }
+
+    /// Runs `fut` retrying all of its requests as if they were idempotent, also after timeouts,
+    /// e.g. `client.retry_anyway(client.create_stream_marker(&id, None))`.
+    ///
+    /// Only use this when sending a request twice does no harm.
+    pub async fn retry_anyway<F: Future>(&self, fut: F) -> F::Output {
+        RETRY_ANYWAY.scope(true, fut).await
+    }
+
+    /// Limits how many requests are sent at the same time, defaults to
+    /// [`DEFAULT_MAX_IN_FLIGHT`](super::DEFAULT_MAX_IN_FLIGHT).
+    ///
+    /// Methods sending several requests, e.g. `get_streams` for many users, send up to this many
+    /// at once, more wait for a previous one to finish.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        let max_in_flight = max_in_flight.max(1);
+        self.in_flight = Semaphore::new(max_in_flight);
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    /// Replaces the default [`RetryPolicy`].
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Runs `request` until it is not rate limited anymore or the retry policy gives up.
+    ///
+    /// Requests for operations that are not idempotent, see [`Operation::is_idempotent`], are
+    /// only retried when the policy allows it. Every attempt can take at most the request
+    /// timeout, timed out attempts are only retried for idempotent operations, as Twitch could
+    /// have processed them.
+    pub(crate) async fn retrying<T, F, Fut>(
+        &self,
+        op: Operation,
+        mut request: F,
+    ) -> Result<T, TwitchCtlError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, TwitchCtlError>>,
+    {
+        let policy = &self.retry_policy;
+        let idempotent =
+            op.is_idempotent() || RETRY_ANYWAY.try_with(|anyway| *anyway).unwrap_or(false);
+        let mut attempt = 0;
+        loop {
+            let res = {
+                let _permit = self.in_flight.acquire().await;
+                match self.request_timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, request())
+                        .await
+                        .unwrap_or(Err(TwitchCtlError::Timeout(timeout))),
+                    None => request().await,
+                }
+            };
+            match res {
+                Err(TwitchCtlError::RateLimited { .. })
+                    if attempt < policy.max_retries
+                        && (idempotent || policy.retry_non_idempotent) =>
+                {
+                    tokio::time::sleep(policy.delay(attempt, self.rate_limit.retry_after())).await;
+                    attempt += 1;
+                }
+                Err(TwitchCtlError::Timeout(_)) if attempt < policy.max_retries && idempotent => {
+                    tokio::time::sleep(policy.delay(attempt, None)).await;
+                    attempt += 1;
+                }
+                Err(TwitchCtlError::RateLimited { .. }) => {
+                    return Err(TwitchCtlError::RateLimited {
+                        retry_after: self.rate_limit.retry_after(),
+                    })
+                }
+                res => return res,
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use http::Method;
+use serde::{Deserialize, Serialize};
+use twitch_api2::types::{CategoryId, UserId};
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// A planned stream in the schedule of a broadcaster
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScheduleSegment {
+    pub id: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub title: String,
+    /// set if the stream was canceled
+    pub canceled_until: Option<String>,
+    pub category: Option<ScheduleCategory>,
+    pub is_recurring: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScheduleCategory {
+    pub id: CategoryId,
+    pub name: String,
+}
+
+/// A new segment for the schedule
+#[derive(Serialize, Debug, Clone)]
+pub struct NewScheduleSegment {
+    /// RFC3339 timestamp, e.g. `2021-07-01T18:00:00Z`
+    pub start_time: String,
+    /// IANA time zone, e.g. `Europe/Berlin`, used for recurring segments
+    pub timezone: String,
+    /// the length in minutes, between 30 and 1380
+    pub duration: u32,
+    pub is_recurring: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<CategoryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Changes to a segment, only the fields that are set are changed
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ScheduleSegmentPatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<CategoryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_canceled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct Schedule {
+    segments: Option<Vec<ScheduleSegment>>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns the upcoming segments of the schedule of a broadcaster.
+    pub async fn get_schedule(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<ScheduleSegment>, TwitchCtlError> {
+        let mut segments = vec![];
+        let mut pagination = None;
+        loop {
+            let mut query = vec![
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("first", "25".to_string()),
+            ];
+            if let Some(cursor) = pagination {
+                query.push(("after", cursor));
+            }
+            let res = match self
+                .helix_data::<Schedule, _>(
+                    Operation::GetSchedule,
+                    Method::GET,
+                    "schedule",
+                    &query,
+                    NO_BODY,
+                )
+                .await
+            {
+                Ok(res) => res,
+                // Twitch responds with 404 if there is no schedule
+                Err(TwitchCtlError::NotFound(_)) => break,
+                Err(e) => return Err(e),
+            };
+            segments.append(&mut res.data.segments.unwrap_or_default());
+            pagination = res.pagination.cursor;
+            if pagination == None {
+                break;
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Adds a segment to the schedule of a broadcaster and returns it, or `None` in a dry run.
+    pub async fn create_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment: NewScheduleSegment,
+    ) -> Result<Option<ScheduleSegment>, TwitchCtlError> {
+        validate(Some(segment.duration), segment.title.as_deref())?;
+        self.helix_write::<Schedule, _>(
+            Operation::CreateScheduleSegment,
+            Method::POST,
+            "schedule/segment",
+            &[("broadcaster_id", broadcaster_id.to_string())],
+            Some(&segment),
+        )
+        .await?
+        .map(|res| {
+            res.data
+                .segments
+                .and_then(|segments| segments.into_iter().next())
+                .ok_or_else(|| {
+                    TwitchCtlError::Helix("Twitch did not return the created segment".into())
+                })
+        })
+        .transpose()
+    }
+
+    pub async fn update_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+        segment: ScheduleSegmentPatch,
+    ) -> Result<(), TwitchCtlError> {
+        validate(segment.duration, segment.title.as_deref())?;
+        self.helix_send(
+            Operation::ManageSchedule,
+            Method::PATCH,
+            "schedule/segment",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("id", segment_id.to_string()),
+            ],
+            Some(&segment),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        self.helix_send(
+            Operation::ManageSchedule,
+            Method::DELETE,
+            "schedule/segment",
+            &[
+                ("broadcaster_id", broadcaster_id.to_string()),
+                ("id", segment_id.to_string()),
+            ],
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+fn validate(duration: Option<u32>, title: Option<&str>) -> Result<(), TwitchCtlError> {
+    if let Some(duration) = duration {
+        if !(30..=1380).contains(&duration) {
+            return Err(TwitchCtlError::Validation(format!(
+                "A segment has to be 30 to 1380 minutes long, got {}.",
+                duration
+            )));
+        }
+    }
+    if title.map_or(0, |title| title.chars().count()) > 140 {
+        return Err(TwitchCtlError::Validation(
+            "Segment titles can not be longer than 140 characters.".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use futures::{stream, StreamExt, TryStreamExt};
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, future::Future, time::Duration};
+use twitch_api2::{
+    helix::streams::{GetStreamsRequest, Stream},
+    types::UserId,
+};
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// A marker in the stream for editing the VOD later
+#[derive(Deserialize, Debug, Clone)]
+pub struct StreamMarker {
+    pub id: String,
+    pub created_at: String,
+    pub description: String,
+    /// the position in the stream in seconds
+    pub position_seconds: u64,
+}
+
+/// The state of a hype train
+#[derive(Deserialize, Debug, Clone)]
+pub struct HypeTrain {
+    pub level: u32,
+    /// the points contributed to the current level
+    pub total: u64,
+    /// the points needed to reach the next level
+    pub goal: u64,
+    pub started_at: String,
+    pub expires_at: String,
+}
+
+/// Whether a broadcaster is streaming, see [`ApiClient::watch_stream_status`]
+#[derive(Debug, Clone)]
+pub enum StreamStatus {
+    Live(Stream),
+    Offline,
+}
+
+#[derive(Deserialize)]
+struct HypeTrainEvent {
+    event_data: HypeTrain,
+}
+
+#[derive(Serialize)]
+struct StreamMarkerBody<'s> {
+    user_id: &'s UserId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns the stream of a broadcaster, or `None` if they are offline.
+    pub async fn get_stream(&self, user_id: &UserId) -> Result<Option<Stream>, TwitchCtlError> {
+        self.cached(format!("streams?user_id={}", user_id), || async {
+            Ok(self.get_streams(&[user_id]).await?.into_iter().next())
+        })
+        .await
+    }
+
+    /// Returns the streams of all broadcasters in `user_ids` that are live.
+    pub async fn get_streams(&self, user_ids: &[&UserId]) -> Result<Vec<Stream>, TwitchCtlError> {
+        // Twitch accepts up to 100 ids per request
+        stream::iter(user_ids.chunks(100))
+            .map(|user_ids| async move {
+                let req = GetStreamsRequest::builder()
+                    .user_id(user_ids.iter().cloned().cloned().collect::<Vec<_>>())
+                    .build();
+                Ok::<_, TwitchCtlError>(self.req_get(Operation::GetStreams, req).await?.data)
+            })
+            .buffered(self.max_in_flight)
+            .try_concat()
+            .await
+    }
+
+    /// Whether the broadcaster is streaming right now.
+    pub async fn is_live(&self, user_id: &UserId) -> Result<bool, TwitchCtlError> {
+        Ok(self.get_stream(user_id).await?.is_some())
+    }
+
+    /// Whether each of the broadcasters in `user_ids` is streaming right now.
+    pub async fn are_live(
+        &self,
+        user_ids: &[&UserId],
+    ) -> Result<HashMap<UserId, bool>, TwitchCtlError> {
+        let mut live: HashMap<_, _> = user_ids.iter().map(|id| ((*id).clone(), false)).collect();
+        for stream in self.get_streams(user_ids).await? {
+            live.insert(stream.user_id, true);
+        }
+        Ok(live)
+    }
+
+    /// Checks the stream of a broadcaster every `interval` until `shutdown` completes,
+    /// calling `on_change` whenever it went live or offline.
+    ///
+    /// The status at the start is only remembered, `on_change` is not called for it.
+    pub async fn watch_stream_status<F, S>(
+        &self,
+        user_id: &UserId,
+        interval: Duration,
+        mut on_change: F,
+        shutdown: S,
+    ) -> Result<(), TwitchCtlError>
+    where
+        F: FnMut(StreamStatus),
+        S: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+        let mut interval = tokio::time::interval(interval);
+        let mut was_live = None;
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                _ = interval.tick() => {}
+            }
+            let stream = self.uncached(self.get_stream(user_id)).await?;
+            let is_live = stream.is_some();
+            if was_live.map_or(false, |was_live| was_live != is_live) {
+                on_change(match stream {
+                    Some(stream) => StreamStatus::Live(stream),
+                    None => StreamStatus::Offline,
+                });
+            }
+            was_live = Some(is_live);
+        }
+    }
+
+    /// Creates a marker at the current position of a live stream.
+    ///
+    /// Returns the created marker, or `None` in a dry run.
+    pub async fn create_stream_marker(
+        &self,
+        user_id: &UserId,
+        description: Option<String>,
+    ) -> Result<Option<StreamMarker>, TwitchCtlError> {
+        if description.as_ref().map_or(0, |d| d.chars().count()) > 140 {
+            return Err(TwitchCtlError::Validation(
+                "Marker descriptions can not be longer than 140 characters.".to_string(),
+            ));
+        }
+        let res = self
+            .helix_write::<Vec<StreamMarker>, _>(
+                Operation::CreateStreamMarker,
+                Method::POST,
+                "streams/markers",
+                &[],
+                Some(&StreamMarkerBody {
+                    user_id,
+                    description,
+                }),
+            )
+            .await
+            .map_err(|e| match e {
+                TwitchCtlError::NotFound(_) => TwitchCtlError::NotLive("create a marker"),
+                e => e,
+            })?;
+        res.map(|res| {
+            res.data.into_iter().next().ok_or_else(|| {
+                TwitchCtlError::Helix("Twitch did not return the created marker".into())
+            })
+        })
+        .transpose()
+    }
+
+    /// Returns the current or most recent hype train of a broadcaster.
+    ///
+    /// With `history` all recorded hype train events are returned, most recent first.
+    pub async fn get_hype_train_events(
+        &self,
+        broadcaster_id: &UserId,
+        history: bool,
+    ) -> Result<Vec<HypeTrain>, TwitchCtlError> {
+        let query = [("broadcaster_id", broadcaster_id.to_string())];
+        let events: Vec<HypeTrainEvent> = if history {
+            self.helix_pages(
+                Operation::GetHypeTrainEvents,
+                "hypetrain/events",
+                &query,
+                None,
+            )
+            .await?
+            .data
+        } else {
+            self.helix_data(
+                Operation::GetHypeTrainEvents,
+                Method::GET,
+                "hypetrain/events",
+                &[query[0].clone(), ("first", "1".to_string())],
+                NO_BODY,
+            )
+            .await?
+            .data
+        };
+        Ok(events.into_iter().map(|event| event.event_data).collect())
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Deserialize;
+use twitch_api2::types::UserId;
+
+use super::{
+    helix::{Page, Paged},
+    ApiClient, Operation,
+};
+use crate::error::TwitchCtlError;
+
+/// A user subscribed to a channel
+#[derive(Deserialize, Debug, Clone)]
+pub struct Subscriber {
+    pub user_id: UserId,
+    pub user_name: String,
+    /// `1000`, `2000` or `3000` for tier 1, 2 and 3
+    pub tier: String,
+    pub is_gift: bool,
+    /// empty if the subscription is not a gift
+    pub gifter_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChannelSubscriptions {
+    /// the total number of subscribers
+    pub total: u64,
+    /// the subscriber points, higher tiers count more
+    pub points: u64,
+    pub subscribers: Vec<Subscriber>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns all subscribers of a broadcaster.
+    pub async fn get_subscriptions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChannelSubscriptions, TwitchCtlError> {
+        let res = self
+            .helix_pages(
+                Operation::GetSubscriptions,
+                "subscriptions",
+                &[("broadcaster_id", broadcaster_id.to_string())],
+                None,
+            )
+            .await?;
+        Ok(ChannelSubscriptions {
+            total: res.total.unwrap_or(res.data.len() as u64),
+            points: res.points.unwrap_or_default(),
+            subscribers: res.data,
+        })
+    }
+
+    /// Returns a page of the subscribers of a broadcaster.
+    pub async fn get_subscriptions_page(
+        &self,
+        broadcaster_id: &UserId,
+        page: &Page,
+    ) -> Result<Paged<Subscriber>, TwitchCtlError> {
+        self.helix_page(
+            Operation::GetSubscriptions,
+            "subscriptions",
+            &[("broadcaster_id", broadcaster_id.to_string())],
+            page,
+        )
+        .await
+    }
+}
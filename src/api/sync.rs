@@ -0,0 +1,138 @@
+use twitch_api2::{
+    helix::points::{CustomReward, UpdateCustomRewardBody},
+    types::UserId,
+};
+
+use super::{normalize_title, ApiClient, RewardSpec, TwitchApi};
+use crate::error::TwitchCtlError;
+
+/// The titles of the rewards [`ApiClient::sync_rewards`] touched
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl<'a> ApiClient<'a> {
+    /// Makes the rewards of a broadcaster match `desired`.
+    ///
+    /// Rewards are matched by their title, ignoring case and diacritics. Missing rewards are
+    /// created and differing ones are updated. With `delete_extras` the rewards not in `desired`
+    /// are deleted. Only rewards created with the client id of the token are considered, as
+    /// Twitch does not allow changing the other ones.
+    pub async fn sync_rewards(
+        &self,
+        broadcaster_id: &UserId,
+        desired: Vec<RewardSpec>,
+        delete_extras: bool,
+    ) -> Result<SyncReport, TwitchCtlError> {
+        sync(self, broadcaster_id, desired, delete_extras).await
+    }
+}
+
+/// [`ApiClient::sync_rewards`] for any [`TwitchApi`]
+pub(super) async fn sync<A: TwitchApi + ?Sized>(
+    api: &A,
+    broadcaster_id: &UserId,
+    desired: Vec<RewardSpec>,
+    delete_extras: bool,
+) -> Result<SyncReport, TwitchCtlError> {
+    let mut existing: Vec<_> = api
+        .get_manageable_rewards(broadcaster_id)
+        .await?
+        .into_iter()
+        .map(Some)
+        .collect();
+    let mut report = SyncReport::default();
+    for spec in desired {
+        let title = normalize_title(&spec.title);
+        let matching = existing.iter_mut().find(|reward| {
+            reward
+                .as_ref()
+                .map_or(false, |reward| normalize_title(&reward.title) == title)
+        });
+        match matching.and_then(Option::take) {
+            Some(reward) if spec.matches(&reward) => report.unchanged.push(spec.title),
+            Some(reward) => {
+                api.update_custom_reward(broadcaster_id, &reward.id, spec.to_update_body())
+                    .await?;
+                report.updated.push(spec.title);
+            }
+            None => {
+                let title = spec.title.clone();
+                api.create_custom_reward(broadcaster_id, spec.into())
+                    .await?;
+                report.created.push(title);
+            }
+        }
+    }
+    if delete_extras {
+        for reward in existing.into_iter().flatten() {
+            api.delete_custom_reward(broadcaster_id, &reward.id).await?;
+            report.deleted.push(reward.title);
+        }
+    }
+    Ok(report)
+}
+
+impl RewardSpec {
+    /// Whether `reward` already has the settings of the spec
+    fn matches(&self, reward: &CustomReward) -> bool {
+        let limit = |enabled: bool, value: Option<usize>, current_enabled: bool, current: u64| {
+            enabled == current_enabled && (!enabled || value.unwrap_or(0) as u64 == current)
+        };
+        self.title == reward.title
+            && self.cost as u64 == reward.cost as u64
+            && self
+                .prompt
+                .as_ref()
+                .map_or(true, |prompt| prompt == &reward.prompt)
+            && self.is_enabled == reward.is_enabled
+            && self.background_color.as_ref().map_or(true, |color| {
+                color.eq_ignore_ascii_case(&reward.background_color)
+            })
+            && self.is_user_input_required == reward.is_user_input_required
+            && limit(
+                self.is_max_per_stream_enabled,
+                self.max_per_stream,
+                reward.max_per_stream_setting.is_enabled,
+                reward.max_per_stream_setting.max_per_stream as u64,
+            )
+            && limit(
+                self.is_max_per_user_per_stream_enabled,
+                self.max_per_user_per_stream,
+                reward.max_per_user_per_stream_setting.is_enabled,
+                reward
+                    .max_per_user_per_stream_setting
+                    .max_per_user_per_stream as u64,
+            )
+            && limit(
+                self.is_global_cooldown_enabled,
+                self.global_cooldown_seconds,
+                reward.global_cooldown_setting.is_enabled,
+                reward.global_cooldown_setting.global_cooldown_seconds as u64,
+            )
+            && self.should_redemptions_skip_request_queue
+                == reward.should_redemptions_skip_request_queue
+    }
+
+    fn to_update_body(&self) -> UpdateCustomRewardBody {
+        UpdateCustomRewardBody::builder()
+            .title(Some(self.title.clone()))
+            .cost(Some(self.cost))
+            .prompt(self.prompt.clone())
+            .is_enabled(Some(self.is_enabled))
+            .background_color(self.background_color.clone())
+            .is_user_input_required(Some(self.is_user_input_required))
+            .is_max_per_stream_enabled(Some(self.is_max_per_stream_enabled))
+            .max_per_stream(self.max_per_stream)
+            .is_max_per_user_per_stream_enabled(Some(self.is_max_per_user_per_stream_enabled))
+            .max_per_user_per_stream(self.max_per_user_per_stream)
+            .is_global_cooldown_enabled(Some(self.is_global_cooldown_enabled))
+            .global_cooldown_seconds(self.global_cooldown_seconds)
+            .should_redemptions_skip_request_queue(Some(self.should_redemptions_skip_request_queue))
+            .build()
+    }
+}
@@ -0,0 +1,90 @@
+use std::time::{Duration, SystemTime};
+use twitch_api2::{
+    twitch_oauth2::{
+        AccessToken, AppAccessToken, ClientId, RefreshToken, Scope, TwitchToken, UserToken,
+    },
+    types::UserId,
+};
+
+use super::{retry_validation, transport::oauth_http_client, ApiClient};
+use crate::error::TwitchCtlError;
+
+/// What Twitch currently knows about the token of the client
+#[derive(Debug, Clone)]
+pub struct TokenStatus {
+    /// `None` for app access tokens
+    pub login: Option<String>,
+    /// `None` for app access tokens
+    pub user_id: Option<UserId>,
+    pub scopes: Vec<Scope>,
+    /// `None` if Twitch did not tell when the token expires
+    pub expires_in: Option<Duration>,
+    pub expires_at: Option<SystemTime>,
+}
+
+/// The token requests are authorized with
+#[derive(Debug)]
+pub(crate) enum ClientToken {
+    User(UserToken),
+    /// only allows requests that do not act on behalf of a user
+    App(AppAccessToken),
+}
+
+/// Evaluates `$body` with `$token` bound to the inner token, whichever kind it is
+macro_rules! with_token {
+    ($client_token:expr, |$token:ident| $body:expr) => {
+        match $client_token {
+            $crate::api::token::ClientToken::User($token) => $body,
+            $crate::api::token::ClientToken::App($token) => $body,
+        }
+    };
+}
+
+impl ClientToken {
+    pub(crate) fn access_token(&self) -> &AccessToken {
+        with_token!(self, |token| token.token())
+    }
+
+    pub(crate) fn client_id(&self) -> &ClientId {
+        with_token!(self, |token| token.client_id())
+    }
+
+    /// `None` for app access tokens, they are renewed without one
+    pub(crate) fn refresh_token(&self) -> Option<&RefreshToken> {
+        match self {
+            ClientToken::User(token) => token.refresh_token.as_ref(),
+            ClientToken::App(_) => None,
+        }
+    }
+
+    pub(crate) fn expires_in(&self) -> Duration {
+        with_token!(self, |token| token.expires_in())
+    }
+
+    pub(crate) async fn refresh(&mut self) -> Result<(), TwitchCtlError> {
+        with_token!(self, |token| token.refresh_token(oauth_http_client).await?);
+        Ok(())
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Asks Twitch whether the token is still valid and when it expires.
+    ///
+    /// Fails with [`TwitchCtlError::Auth`] if the token was revoked or expired.
+    pub async fn token_status(&self) -> Result<TokenStatus, TwitchCtlError> {
+        let token = self.token().await?;
+        let validated = retry_validation(|| {
+            with_token!(&*token, |token| token.validate_token(oauth_http_client))
+        })
+        .await?;
+        Ok(TokenStatus {
+            login: validated.login.map(|login| login.to_string()),
+            user_id: validated.user_id,
+            scopes: validated.scopes.unwrap_or_default(),
+            expires_in: validated.expires_in,
+            expires_at: validated
+                .expires_in
+                .map(|expires_in| SystemTime::now() + expires_in),
+        })
+    }
+}
@@ -0,0 +1,81 @@
+//! Instrumentation of the Helix requests, only active with the `tracing` feature
+
+use serde::Serialize;
+use std::future::Future;
+
+use crate::error::TwitchCtlError;
+
+/// Runs a single Helix request inside a span recording the endpoint and broadcaster,
+/// and logs its latency and outcome.
+#[cfg(feature = "tracing")]
+pub(crate) async fn traced<T, Fut>(
+    endpoint: &str,
+    broadcaster_id: Option<String>,
+    request: Fut,
+) -> Result<T, TwitchCtlError>
+where
+    Fut: Future<Output = Result<T, TwitchCtlError>>,
+{
+    use tracing::Instrument;
+
+    let span = tracing::debug_span!(
+        "helix_request",
+        endpoint,
+        broadcaster_id = broadcaster_id.as_deref().unwrap_or_default()
+    );
+    let start = std::time::Instant::now();
+    let res = request.instrument(span.clone()).await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+    match &res {
+        Ok(_) => tracing::debug!(parent: &span, latency_ms, "request succeeded"),
+        Err(e) => tracing::warn!(parent: &span, latency_ms, error = %e, "request failed"),
+    }
+    res
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn traced<T, Fut>(
+    _endpoint: &str,
+    _broadcaster_id: Option<String>,
+    request: Fut,
+) -> Result<T, TwitchCtlError>
+where
+    Fut: Future<Output = Result<T, TwitchCtlError>>,
+{
+    request.await
+}
+
+/// Reads the `broadcaster_id` query parameter of a twitch_api2 request
+pub(crate) fn broadcaster_id<R: Serialize>(req: &R) -> Option<String> {
+    if !cfg!(feature = "tracing") {
+        return None;
+    }
+    serde_json::to_value(req)
+        .ok()?
+        .get("broadcaster_id")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Reads the `broadcaster_id` of a raw Helix query
+pub(crate) fn query_broadcaster_id(query: &[(&str, String)]) -> Option<String> {
+    query
+        .iter()
+        .find(|(key, _)| key.ends_with("broadcaster_id"))
+        .map(|(_, value)| value.clone())
+}
+
+/// Logs the rate limit headers of a Helix response
+#[cfg(feature = "tracing")]
+pub(crate) fn rate_limit(headers: &http::HeaderMap) {
+    let header = |name| super::retry::header::<String>(headers, name).unwrap_or_default();
+    tracing::debug!(
+        limit = header("Ratelimit-Limit").as_str(),
+        remaining = header("Ratelimit-Remaining").as_str(),
+        reset = header("Ratelimit-Reset").as_str(),
+        "rate limit"
+    );
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn rate_limit(_headers: &http::HeaderMap) {}
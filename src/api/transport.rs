@@ -0,0 +1,74 @@
+//! The HTTP client all requests are sent with, selected by the `surf` (default) or `reqwest`
+//! feature
+
+use twitch_api2::client::{BoxedFuture, Req, Response};
+
+use super::{helix::BaseUrl, retry::RateLimitTracker};
+
+#[cfg(not(any(feature = "surf", feature = "reqwest")))]
+compile_error!("either the `surf` or the `reqwest` feature has to be enabled");
+
+/// The client requests are sent with, a `surf::Client` unless only the `reqwest` feature is
+/// enabled
+#[cfg(feature = "surf")]
+pub type HttpClient = surf::Client;
+/// The client requests are sent with, a `surf::Client` unless only the `reqwest` feature is
+/// enabled
+#[cfg(all(feature = "reqwest", not(feature = "surf")))]
+pub type HttpClient = reqwest::Client;
+
+#[cfg(feature = "surf")]
+type ClientError = twitch_api2::client::SurfError;
+#[cfg(all(feature = "reqwest", not(feature = "surf")))]
+type ClientError = reqwest::Error;
+
+#[cfg(all(feature = "reqwest", not(feature = "surf")))]
+pub(crate) use twitch_oauth2::client::reqwest_http_client as oauth_http_client;
+#[cfg(feature = "surf")]
+pub(crate) use twitch_oauth2::client::surf_http_client as oauth_http_client;
+
+/// A request that could not be sent
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum TransportError {
+    #[error("{0}")]
+    Client(#[source] ClientError),
+    #[error("Invalid URL: {0}")]
+    Url(#[from] url::ParseError),
+    #[error("Invalid URI: {0}")]
+    Uri(#[from] http::uri::InvalidUri),
+}
+
+/// Sends the Helix requests of twitch_api2 and of [`ApiClient`](super::ApiClient) with
+/// [`HttpClient`] to the [`BaseUrl`] and remembers the rate limit of the responses.
+#[derive(Clone)]
+pub(crate) struct Transport {
+    client: HttpClient,
+    base_url: BaseUrl,
+    rate_limit: RateLimitTracker,
+}
+
+impl Transport {
+    pub(crate) fn new(client: HttpClient, base_url: BaseUrl, rate_limit: RateLimitTracker) -> Self {
+        Transport {
+            client,
+            base_url,
+            rate_limit,
+        }
+    }
+}
+
+impl<'a> twitch_api2::HttpClient<'a> for Transport {
+    type Error = TransportError;
+
+    fn req(&'a self, mut request: Req) -> BoxedFuture<'a, Result<Response, TransportError>> {
+        if let Err(e) = self.base_url.rewrite(&mut request) {
+            return Box::pin(async { Err(e) });
+        }
+        let res = twitch_api2::HttpClient::req(&self.client, request);
+        Box::pin(async move {
+            let res = res.await.map_err(TransportError::Client)?;
+            self.rate_limit.track(res.headers());
+            Ok(res)
+        })
+    }
+}
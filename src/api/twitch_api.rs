@@ -0,0 +1,934 @@
+use async_trait::async_trait;
+use twitch_api2::{
+    helix::{
+        games::Game,
+        points::{
+            CreateCustomRewardBody, CustomReward, CustomRewardRedemption,
+            CustomRewardRedemptionStatus, UpdateCustomRewardBody,
+        },
+        search::search_categories::Category,
+        streams::Stream,
+        tags::TwitchTag,
+    },
+    types::{CategoryId, RedemptionId, RewardId, TagId, UserId, VideoId},
+};
+
+use super::{
+    edit_distance, lookup_in_rewards, normalize_title, sync, AnnouncementColor, ApiClient,
+    AutoModResult, BlockContext, BlockReason, BlockedTerm, BlockedUser, CategoryMatch,
+    ChannelFollowers, ChannelInfo, ChannelSubscriptions, ChatSettings, ChatSettingsPatch,
+    Commercial, CreatedClip, CreatorGoal, HypeTrain, NewScheduleSegment, Poll, PollEnd, Prediction,
+    PredictionStatus, Raid, RewardLookup, RewardSpec, ScheduleSegment, ScheduleSegmentPatch,
+    ShieldModeStatus, StreamMarker, SyncReport, UserIdent, Video, VideoType,
+};
+use crate::error::TwitchCtlError;
+
+/// The requests to Twitch used for managing a stream.
+///
+/// Implemented by [`ApiClient`] and, with the `test-util` feature, by `MockApiClient`, so code
+/// using the client can be tested without talking to Twitch.
+#[async_trait(?Send)]
+pub trait TwitchApi {
+    async fn get_broadcaster_id(
+        &self,
+        broadcaster_ident: UserIdent,
+    ) -> Result<UserId, TwitchCtlError>;
+
+    async fn get_channel_information(&self, id: &UserId) -> Result<ChannelInfo, TwitchCtlError>;
+
+    async fn modify_channel_information(
+        &self,
+        id: &UserId,
+        info: ChannelInfo,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn search_categories(
+        &self,
+        term: &str,
+        max: usize,
+        match_mode: CategoryMatch,
+    ) -> Result<Option<Vec<Category>>, TwitchCtlError>;
+
+    async fn get_stream(&self, user_id: &UserId) -> Result<Option<Stream>, TwitchCtlError>;
+
+    async fn get_stream_tags(&self, id: &UserId) -> Result<Vec<TwitchTag>, TwitchCtlError>;
+
+    async fn get_rewards(&self, id: &UserId) -> Result<Vec<CustomReward>, TwitchCtlError>;
+
+    async fn create_custom_reward(
+        &self,
+        id: &UserId,
+        reward: CreateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn update_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        reward: UpdateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn delete_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn get_redemptions(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<Vec<CustomRewardRedemption>, TwitchCtlError>;
+
+    async fn update_redemption_status(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        redemption_ids: &[RedemptionId],
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn replace_stream_tags(
+        &self,
+        broadcaster_id: &UserId,
+        tag_ids: Vec<TagId>,
+    ) -> Result<Vec<TagId>, TwitchCtlError>;
+
+    async fn set_stream_tags_by_name(
+        &self,
+        broadcaster_id: &UserId,
+        tag_names: &[String],
+        locale: Option<&str>,
+    ) -> Result<Vec<String>, TwitchCtlError>;
+
+    async fn get_all_tags(&self) -> Result<Vec<TwitchTag>, TwitchCtlError>;
+
+    async fn resolve_tag_names(
+        &self,
+        tags: &[String],
+        locales: Option<&[&str]>,
+    ) -> Result<(Vec<TagId>, Vec<String>), TwitchCtlError>;
+
+    async fn start_commercial(
+        &self,
+        broadcaster_id: &UserId,
+        length: u32,
+    ) -> Result<Option<Commercial>, TwitchCtlError>;
+
+    async fn get_manageable_rewards(
+        &self,
+        id: &UserId,
+    ) -> Result<Vec<CustomReward>, TwitchCtlError>;
+
+    async fn get_channel_followers(
+        &self,
+        broadcaster_id: &UserId,
+        all: bool,
+    ) -> Result<ChannelFollowers, TwitchCtlError>;
+
+    async fn get_creator_goals(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<CreatorGoal>, TwitchCtlError>;
+
+    async fn send_announcement(
+        &self,
+        broadcaster_id: &UserId,
+        message: &str,
+        color: AnnouncementColor,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn send_shoutout(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn get_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChatSettings, TwitchCtlError>;
+
+    async fn update_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+        settings: ChatSettingsPatch,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn send_whisper(
+        &self,
+        to_user: UserIdent,
+        message: &str,
+        new_conversation: bool,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn create_clip(
+        &self,
+        broadcaster_id: &UserId,
+        has_delay: bool,
+    ) -> Result<Option<CreatedClip>, TwitchCtlError>;
+
+    async fn get_top_games(&self, max: usize) -> Result<Vec<Game>, TwitchCtlError>;
+
+    async fn get_games(
+        &self,
+        ids: &[&CategoryId],
+        names: &[&str],
+    ) -> Result<Vec<Game>, TwitchCtlError>;
+
+    async fn check_automod(
+        &self,
+        broadcaster_id: &UserId,
+        messages: Vec<(String, String)>,
+    ) -> Result<Vec<AutoModResult>, TwitchCtlError>;
+
+    async fn add_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        text: &str,
+    ) -> Result<Option<BlockedTerm>, TwitchCtlError>;
+
+    async fn get_blocked_terms(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<BlockedTerm>, TwitchCtlError>;
+
+    async fn remove_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        term_id: &str,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn delete_chat_message(
+        &self,
+        broadcaster_id: &UserId,
+        message_id: &str,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn clear_chat(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError>;
+
+    async fn get_shield_mode_status(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ShieldModeStatus, TwitchCtlError>;
+
+    async fn set_shield_mode(
+        &self,
+        broadcaster_id: &UserId,
+        active: bool,
+    ) -> Result<Option<ShieldModeStatus>, TwitchCtlError>;
+
+    async fn add_vip(&self, broadcaster_id: &UserId, user: UserIdent)
+        -> Result<(), TwitchCtlError>;
+
+    async fn remove_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn get_vips(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError>;
+
+    async fn add_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn remove_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn get_moderators(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError>;
+
+    async fn create_poll(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        choices: Vec<String>,
+        duration: u32,
+        channel_points_voting: Option<u32>,
+    ) -> Result<Option<Poll>, TwitchCtlError>;
+
+    async fn get_polls(&self, broadcaster_id: &UserId) -> Result<Vec<Poll>, TwitchCtlError>;
+
+    async fn end_poll(
+        &self,
+        broadcaster_id: &UserId,
+        poll_id: &str,
+        status: PollEnd,
+    ) -> Result<Option<Poll>, TwitchCtlError>;
+
+    async fn create_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        outcomes: Vec<String>,
+        window: u32,
+    ) -> Result<Option<Prediction>, TwitchCtlError>;
+
+    async fn get_predictions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<Prediction>, TwitchCtlError>;
+
+    async fn end_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        prediction_id: &str,
+        status: PredictionStatus,
+        winning_outcome_id: Option<&str>,
+    ) -> Result<Option<Prediction>, TwitchCtlError>;
+
+    async fn start_raid(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<Option<Raid>, TwitchCtlError>;
+
+    async fn cancel_raid(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError>;
+
+    async fn get_schedule(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<ScheduleSegment>, TwitchCtlError>;
+
+    async fn create_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment: NewScheduleSegment,
+    ) -> Result<Option<ScheduleSegment>, TwitchCtlError>;
+
+    async fn update_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+        segment: ScheduleSegmentPatch,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn delete_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn create_stream_marker(
+        &self,
+        user_id: &UserId,
+        description: Option<String>,
+    ) -> Result<Option<StreamMarker>, TwitchCtlError>;
+
+    async fn get_hype_train_events(
+        &self,
+        broadcaster_id: &UserId,
+        history: bool,
+    ) -> Result<Vec<HypeTrain>, TwitchCtlError>;
+
+    async fn get_subscriptions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChannelSubscriptions, TwitchCtlError>;
+
+    async fn block_user(
+        &self,
+        target: UserIdent,
+        source_context: Option<BlockContext>,
+        reason: Option<BlockReason>,
+    ) -> Result<(), TwitchCtlError>;
+
+    async fn unblock_user(&self, target: UserIdent) -> Result<(), TwitchCtlError>;
+
+    async fn get_blocked_users(&self) -> Result<Vec<BlockedUser>, TwitchCtlError>;
+
+    async fn get_videos(
+        &self,
+        user_id: &UserId,
+        video_type: Option<VideoType>,
+    ) -> Result<Vec<Video>, TwitchCtlError>;
+
+    async fn get_video_by_id(&self, id: &VideoId) -> Result<Option<Video>, TwitchCtlError>;
+
+    async fn delete_videos(&self, video_ids: &[&VideoId]) -> Result<Vec<VideoId>, TwitchCtlError>;
+
+    /// See [`ApiClient::search_category`]
+    async fn search_category(&self, term: &str) -> Result<Option<Category>, TwitchCtlError> {
+        Ok(self
+            .search_categories(term, 1, CategoryMatch::Contains)
+            .await?
+            .map(|mut categories| categories.remove(0)))
+    }
+
+    /// See [`ApiClient::suggest_categories`]
+    async fn suggest_categories(
+        &self,
+        name: &str,
+        max: usize,
+    ) -> Result<Vec<Category>, TwitchCtlError> {
+        let mut candidates = self
+            .search_categories(name, 100, CategoryMatch::Contains)
+            .await?
+            .unwrap_or_default();
+        let prefix: String = name.chars().take(3).collect();
+        if candidates.is_empty() && prefix.len() < name.len() {
+            candidates = self
+                .search_categories(&prefix, 100, CategoryMatch::Contains)
+                .await?
+                .unwrap_or_default();
+        }
+        let name = normalize_title(name);
+        candidates
+            .sort_by_cached_key(|category| edit_distance(&name, &normalize_title(&category.name)));
+        candidates.truncate(max);
+        Ok(candidates)
+    }
+
+    /// See [`ApiClient::set_reward_enabled`]
+    async fn set_reward_enabled(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        enabled: bool,
+    ) -> Result<(), TwitchCtlError> {
+        self.update_custom_reward(
+            broadcaster_id,
+            reward_id,
+            UpdateCustomRewardBody::builder()
+                .is_enabled(enabled)
+                .build(),
+        )
+        .await
+    }
+
+    /// See [`ApiClient::set_reward_paused`]
+    async fn set_reward_paused(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        paused: bool,
+    ) -> Result<(), TwitchCtlError> {
+        self.update_custom_reward(
+            broadcaster_id,
+            reward_id,
+            UpdateCustomRewardBody::builder().is_paused(paused).build(),
+        )
+        .await
+    }
+
+    /// See [`ApiClient::sync_rewards`]
+    async fn sync_rewards(
+        &self,
+        broadcaster_id: &UserId,
+        desired: Vec<RewardSpec>,
+        delete_extras: bool,
+    ) -> Result<SyncReport, TwitchCtlError> {
+        sync::sync(self, broadcaster_id, desired, delete_extras).await
+    }
+
+    /// See [`ApiClient::lookup_reward`]
+    async fn lookup_reward(
+        &self,
+        id: &UserId,
+        query: &str,
+    ) -> Result<RewardLookup, TwitchCtlError> {
+        Ok(lookup_in_rewards(self.get_rewards(id).await?, query))
+    }
+
+    /// See [`ApiClient::find_reward`]
+    async fn find_reward(
+        &self,
+        id: &UserId,
+        query: &str,
+    ) -> Result<Option<CustomReward>, TwitchCtlError> {
+        match self.lookup_reward(id, query).await? {
+            RewardLookup::Found(reward) => Ok(Some(reward)),
+            RewardLookup::NotFound | RewardLookup::Ambiguous(_) => Ok(None),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> TwitchApi for ApiClient<'a> {
+    async fn get_broadcaster_id(
+        &self,
+        broadcaster_ident: UserIdent,
+    ) -> Result<UserId, TwitchCtlError> {
+        ApiClient::get_broadcaster_id(self, broadcaster_ident).await
+    }
+
+    async fn get_channel_information(&self, id: &UserId) -> Result<ChannelInfo, TwitchCtlError> {
+        ApiClient::get_channel_information(self, id).await
+    }
+
+    async fn modify_channel_information(
+        &self,
+        id: &UserId,
+        info: ChannelInfo,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::modify_channel_information(self, id, info).await
+    }
+
+    async fn search_categories(
+        &self,
+        term: &str,
+        max: usize,
+        match_mode: CategoryMatch,
+    ) -> Result<Option<Vec<Category>>, TwitchCtlError> {
+        ApiClient::search_categories(self, term, max, match_mode).await
+    }
+
+    async fn get_stream(&self, user_id: &UserId) -> Result<Option<Stream>, TwitchCtlError> {
+        ApiClient::get_stream(self, user_id).await
+    }
+
+    async fn get_stream_tags(&self, id: &UserId) -> Result<Vec<TwitchTag>, TwitchCtlError> {
+        ApiClient::get_stream_tags(self, id).await
+    }
+
+    async fn get_rewards(&self, id: &UserId) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        ApiClient::get_rewards(self, id).await
+    }
+
+    async fn create_custom_reward(
+        &self,
+        id: &UserId,
+        reward: CreateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::create_custom_reward(self, id, reward).await
+    }
+
+    async fn update_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        reward: UpdateCustomRewardBody,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::update_custom_reward(self, broadcaster_id, reward_id, reward).await
+    }
+
+    async fn delete_custom_reward(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::delete_custom_reward(self, broadcaster_id, reward_id).await
+    }
+
+    async fn get_redemptions(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<Vec<CustomRewardRedemption>, TwitchCtlError> {
+        ApiClient::get_redemptions(self, broadcaster_id, reward_id, status).await
+    }
+
+    async fn update_redemption_status(
+        &self,
+        broadcaster_id: &UserId,
+        reward_id: &RewardId,
+        redemption_ids: &[RedemptionId],
+        status: CustomRewardRedemptionStatus,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::update_redemption_status(self, broadcaster_id, reward_id, redemption_ids, status)
+            .await
+    }
+
+    async fn replace_stream_tags(
+        &self,
+        broadcaster_id: &UserId,
+        tag_ids: Vec<TagId>,
+    ) -> Result<Vec<TagId>, TwitchCtlError> {
+        ApiClient::replace_stream_tags(self, broadcaster_id, tag_ids).await
+    }
+
+    async fn set_stream_tags_by_name(
+        &self,
+        broadcaster_id: &UserId,
+        tag_names: &[String],
+        locale: Option<&str>,
+    ) -> Result<Vec<String>, TwitchCtlError> {
+        ApiClient::set_stream_tags_by_name(self, broadcaster_id, tag_names, locale).await
+    }
+
+    async fn get_all_tags(&self) -> Result<Vec<TwitchTag>, TwitchCtlError> {
+        ApiClient::get_all_tags(self).await
+    }
+
+    async fn resolve_tag_names(
+        &self,
+        tags: &[String],
+        locales: Option<&[&str]>,
+    ) -> Result<(Vec<TagId>, Vec<String>), TwitchCtlError> {
+        ApiClient::resolve_tag_names(self, tags, locales).await
+    }
+
+    async fn start_commercial(
+        &self,
+        broadcaster_id: &UserId,
+        length: u32,
+    ) -> Result<Option<Commercial>, TwitchCtlError> {
+        ApiClient::start_commercial(self, broadcaster_id, length).await
+    }
+
+    async fn get_manageable_rewards(
+        &self,
+        id: &UserId,
+    ) -> Result<Vec<CustomReward>, TwitchCtlError> {
+        ApiClient::get_manageable_rewards(self, id).await
+    }
+
+    async fn get_channel_followers(
+        &self,
+        broadcaster_id: &UserId,
+        all: bool,
+    ) -> Result<ChannelFollowers, TwitchCtlError> {
+        ApiClient::get_channel_followers(self, broadcaster_id, all).await
+    }
+
+    async fn get_creator_goals(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<CreatorGoal>, TwitchCtlError> {
+        ApiClient::get_creator_goals(self, broadcaster_id).await
+    }
+
+    async fn send_announcement(
+        &self,
+        broadcaster_id: &UserId,
+        message: &str,
+        color: AnnouncementColor,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::send_announcement(self, broadcaster_id, message, color).await
+    }
+
+    async fn send_shoutout(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::send_shoutout(self, from_broadcaster_id, to).await
+    }
+
+    async fn get_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChatSettings, TwitchCtlError> {
+        ApiClient::get_chat_settings(self, broadcaster_id).await
+    }
+
+    async fn update_chat_settings(
+        &self,
+        broadcaster_id: &UserId,
+        settings: ChatSettingsPatch,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::update_chat_settings(self, broadcaster_id, settings).await
+    }
+
+    async fn send_whisper(
+        &self,
+        to_user: UserIdent,
+        message: &str,
+        new_conversation: bool,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::send_whisper(self, to_user, message, new_conversation).await
+    }
+
+    async fn create_clip(
+        &self,
+        broadcaster_id: &UserId,
+        has_delay: bool,
+    ) -> Result<Option<CreatedClip>, TwitchCtlError> {
+        ApiClient::create_clip(self, broadcaster_id, has_delay).await
+    }
+
+    async fn get_top_games(&self, max: usize) -> Result<Vec<Game>, TwitchCtlError> {
+        ApiClient::get_top_games(self, max).await
+    }
+
+    async fn get_games(
+        &self,
+        ids: &[&CategoryId],
+        names: &[&str],
+    ) -> Result<Vec<Game>, TwitchCtlError> {
+        ApiClient::get_games(self, ids, names).await
+    }
+
+    async fn check_automod(
+        &self,
+        broadcaster_id: &UserId,
+        messages: Vec<(String, String)>,
+    ) -> Result<Vec<AutoModResult>, TwitchCtlError> {
+        ApiClient::check_automod(self, broadcaster_id, messages).await
+    }
+
+    async fn add_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        text: &str,
+    ) -> Result<Option<BlockedTerm>, TwitchCtlError> {
+        ApiClient::add_blocked_term(self, broadcaster_id, text).await
+    }
+
+    async fn get_blocked_terms(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<BlockedTerm>, TwitchCtlError> {
+        ApiClient::get_blocked_terms(self, broadcaster_id).await
+    }
+
+    async fn remove_blocked_term(
+        &self,
+        broadcaster_id: &UserId,
+        term_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::remove_blocked_term(self, broadcaster_id, term_id).await
+    }
+
+    async fn delete_chat_message(
+        &self,
+        broadcaster_id: &UserId,
+        message_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::delete_chat_message(self, broadcaster_id, message_id).await
+    }
+
+    async fn clear_chat(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError> {
+        ApiClient::clear_chat(self, broadcaster_id).await
+    }
+
+    async fn get_shield_mode_status(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ShieldModeStatus, TwitchCtlError> {
+        ApiClient::get_shield_mode_status(self, broadcaster_id).await
+    }
+
+    async fn set_shield_mode(
+        &self,
+        broadcaster_id: &UserId,
+        active: bool,
+    ) -> Result<Option<ShieldModeStatus>, TwitchCtlError> {
+        ApiClient::set_shield_mode(self, broadcaster_id, active).await
+    }
+
+    async fn add_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::add_vip(self, broadcaster_id, user).await
+    }
+
+    async fn remove_vip(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::remove_vip(self, broadcaster_id, user).await
+    }
+
+    async fn get_vips(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        ApiClient::get_vips(self, broadcaster_id).await
+    }
+
+    async fn add_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::add_moderator(self, broadcaster_id, user).await
+    }
+
+    async fn remove_moderator(
+        &self,
+        broadcaster_id: &UserId,
+        user: UserIdent,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::remove_moderator(self, broadcaster_id, user).await
+    }
+
+    async fn get_moderators(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<(UserId, String, String)>, TwitchCtlError> {
+        ApiClient::get_moderators(self, broadcaster_id).await
+    }
+
+    async fn create_poll(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        choices: Vec<String>,
+        duration: u32,
+        channel_points_voting: Option<u32>,
+    ) -> Result<Option<Poll>, TwitchCtlError> {
+        ApiClient::create_poll(
+            self,
+            broadcaster_id,
+            title,
+            choices,
+            duration,
+            channel_points_voting,
+        )
+        .await
+    }
+
+    async fn get_polls(&self, broadcaster_id: &UserId) -> Result<Vec<Poll>, TwitchCtlError> {
+        ApiClient::get_polls(self, broadcaster_id).await
+    }
+
+    async fn end_poll(
+        &self,
+        broadcaster_id: &UserId,
+        poll_id: &str,
+        status: PollEnd,
+    ) -> Result<Option<Poll>, TwitchCtlError> {
+        ApiClient::end_poll(self, broadcaster_id, poll_id, status).await
+    }
+
+    async fn create_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        title: &str,
+        outcomes: Vec<String>,
+        window: u32,
+    ) -> Result<Option<Prediction>, TwitchCtlError> {
+        ApiClient::create_prediction(self, broadcaster_id, title, outcomes, window).await
+    }
+
+    async fn get_predictions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<Prediction>, TwitchCtlError> {
+        ApiClient::get_predictions(self, broadcaster_id).await
+    }
+
+    async fn end_prediction(
+        &self,
+        broadcaster_id: &UserId,
+        prediction_id: &str,
+        status: PredictionStatus,
+        winning_outcome_id: Option<&str>,
+    ) -> Result<Option<Prediction>, TwitchCtlError> {
+        ApiClient::end_prediction(
+            self,
+            broadcaster_id,
+            prediction_id,
+            status,
+            winning_outcome_id,
+        )
+        .await
+    }
+
+    async fn start_raid(
+        &self,
+        from_broadcaster_id: &UserId,
+        to: UserIdent,
+    ) -> Result<Option<Raid>, TwitchCtlError> {
+        ApiClient::start_raid(self, from_broadcaster_id, to).await
+    }
+
+    async fn cancel_raid(&self, broadcaster_id: &UserId) -> Result<(), TwitchCtlError> {
+        ApiClient::cancel_raid(self, broadcaster_id).await
+    }
+
+    async fn get_schedule(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<Vec<ScheduleSegment>, TwitchCtlError> {
+        ApiClient::get_schedule(self, broadcaster_id).await
+    }
+
+    async fn create_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment: NewScheduleSegment,
+    ) -> Result<Option<ScheduleSegment>, TwitchCtlError> {
+        ApiClient::create_schedule_segment(self, broadcaster_id, segment).await
+    }
+
+    async fn update_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+        segment: ScheduleSegmentPatch,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::update_schedule_segment(self, broadcaster_id, segment_id, segment).await
+    }
+
+    async fn delete_schedule_segment(
+        &self,
+        broadcaster_id: &UserId,
+        segment_id: &str,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::delete_schedule_segment(self, broadcaster_id, segment_id).await
+    }
+
+    async fn create_stream_marker(
+        &self,
+        user_id: &UserId,
+        description: Option<String>,
+    ) -> Result<Option<StreamMarker>, TwitchCtlError> {
+        ApiClient::create_stream_marker(self, user_id, description).await
+    }
+
+    async fn get_hype_train_events(
+        &self,
+        broadcaster_id: &UserId,
+        history: bool,
+    ) -> Result<Vec<HypeTrain>, TwitchCtlError> {
+        ApiClient::get_hype_train_events(self, broadcaster_id, history).await
+    }
+
+    async fn get_subscriptions(
+        &self,
+        broadcaster_id: &UserId,
+    ) -> Result<ChannelSubscriptions, TwitchCtlError> {
+        ApiClient::get_subscriptions(self, broadcaster_id).await
+    }
+
+    async fn block_user(
+        &self,
+        target: UserIdent,
+        source_context: Option<BlockContext>,
+        reason: Option<BlockReason>,
+    ) -> Result<(), TwitchCtlError> {
+        ApiClient::block_user(self, target, source_context, reason).await
+    }
+
+    async fn unblock_user(&self, target: UserIdent) -> Result<(), TwitchCtlError> {
+        ApiClient::unblock_user(self, target).await
+    }
+
+    async fn get_blocked_users(&self) -> Result<Vec<BlockedUser>, TwitchCtlError> {
+        ApiClient::get_blocked_users(self).await
+    }
+
+    async fn get_videos(
+        &self,
+        user_id: &UserId,
+        video_type: Option<VideoType>,
+    ) -> Result<Vec<Video>, TwitchCtlError> {
+        ApiClient::get_videos(self, user_id, video_type).await
+    }
+
+    async fn get_video_by_id(&self, id: &VideoId) -> Result<Option<Video>, TwitchCtlError> {
+        ApiClient::get_video_by_id(self, id).await
+    }
+
+    async fn delete_videos(&self, video_ids: &[&VideoId]) -> Result<Vec<VideoId>, TwitchCtlError> {
+        ApiClient::delete_videos(self, video_ids).await
+    }
+}
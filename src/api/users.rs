@@ -0,0 +1,135 @@
+use http::Method;
+use serde::Deserialize;
+use std::str::FromStr;
+use twitch_api2::types::UserId;
+
+use super::{helix::NO_BODY, ApiClient, Operation, UserIdent};
+use crate::error::TwitchCtlError;
+
+/// A user on the block list of the token user
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockedUser {
+    pub user_id: UserId,
+    pub user_login: String,
+    pub display_name: String,
+}
+
+/// Where the blocked user annoyed the token user
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockContext {
+    Chat,
+    Whisper,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockReason {
+    Spam,
+    Harassment,
+    Other,
+}
+
+impl BlockContext {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BlockContext::Chat => "chat",
+            BlockContext::Whisper => "whisper",
+        }
+    }
+}
+
+impl BlockReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BlockReason::Spam => "spam",
+            BlockReason::Harassment => "harassment",
+            BlockReason::Other => "other",
+        }
+    }
+}
+
+impl FromStr for BlockContext {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "chat" => Ok(BlockContext::Chat),
+            "whisper" => Ok(BlockContext::Whisper),
+            _ => Err("unsupported block context"),
+        }
+    }
+}
+
+impl FromStr for BlockReason {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spam" => Ok(BlockReason::Spam),
+            "harassment" => Ok(BlockReason::Harassment),
+            "other" => Ok(BlockReason::Other),
+            _ => Err("unsupported block reason"),
+        }
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Adds a user to the block list of the token user.
+    pub async fn block_user(
+        &self,
+        target: UserIdent,
+        source_context: Option<BlockContext>,
+        reason: Option<BlockReason>,
+    ) -> Result<(), TwitchCtlError> {
+        let target_user_id = self.get_broadcaster_id(target).await?;
+        let mut query = vec![("target_user_id", target_user_id.to_string())];
+        if let Some(source_context) = source_context {
+            query.push(("source_context", source_context.as_str().to_string()));
+        }
+        if let Some(reason) = reason {
+            query.push(("reason", reason.as_str().to_string()));
+        }
+        self.helix_send(
+            Operation::ManageBlockedUsers,
+            Method::PUT,
+            "users/blocks",
+            &query,
+            NO_BODY,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Removes a user from the block list of the token user.
+    ///
+    /// Unblocking a user that is not blocked succeeds.
+    pub async fn unblock_user(&self, target: UserIdent) -> Result<(), TwitchCtlError> {
+        let target_user_id = self.get_broadcaster_id(target).await?;
+        match self
+            .helix_send(
+                Operation::ManageBlockedUsers,
+                Method::DELETE,
+                "users/blocks",
+                &[("target_user_id", target_user_id.to_string())],
+                NO_BODY,
+            )
+            .await
+        {
+            Ok(_) | Err(TwitchCtlError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the block list of the token user.
+    pub async fn get_blocked_users(&self) -> Result<Vec<BlockedUser>, TwitchCtlError> {
+        let user_id = self.get_user_id()?;
+        Ok(self
+            .helix_pages(
+                Operation::GetBlockedUsers,
+                "users/blocks",
+                &[("broadcaster_id", user_id.to_string())],
+                None,
+            )
+            .await?
+            .data)
+    }
+}
@@ -0,0 +1,119 @@
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use twitch_api2::types::{UserId, VideoId};
+
+use super::{helix::NO_BODY, ApiClient, Operation};
+use crate::error::TwitchCtlError;
+
+/// A VOD of a broadcaster
+#[derive(Deserialize, Debug, Clone)]
+pub struct Video {
+    pub id: VideoId,
+    pub user_id: UserId,
+    pub title: String,
+    pub created_at: String,
+    /// e.g. `3h8m33s`
+    pub duration: String,
+    pub view_count: u64,
+    pub url: String,
+    #[serde(rename = "type")]
+    pub video_type: VideoType,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoType {
+    /// a past broadcast
+    Archive,
+    Highlight,
+    Upload,
+}
+
+impl VideoType {
+    pub(super) fn as_str(&self) -> &'static str {
+        match self {
+            VideoType::Archive => "archive",
+            VideoType::Highlight => "highlight",
+            VideoType::Upload => "upload",
+        }
+    }
+}
+
+impl FromStr for VideoType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "archive" => Ok(VideoType::Archive),
+            "highlight" => Ok(VideoType::Highlight),
+            "upload" => Ok(VideoType::Upload),
+            _ => Err("unsupported video type"),
+        }
+    }
+}
+
+impl<'a> ApiClient<'a> {
+    /// Returns all videos of a user, only the ones of `video_type` if given.
+    pub async fn get_videos(
+        &self,
+        user_id: &UserId,
+        video_type: Option<VideoType>,
+    ) -> Result<Vec<Video>, TwitchCtlError> {
+        let mut query = vec![("user_id", user_id.to_string())];
+        if let Some(video_type) = video_type {
+            query.push(("type", video_type.as_str().to_string()));
+        }
+        Ok(self
+            .helix_pages(Operation::GetVideos, "videos", &query, None)
+            .await?
+            .data)
+    }
+
+    /// Returns the video with the `id`, or `None` if it does not exist.
+    pub async fn get_video_by_id(&self, id: &VideoId) -> Result<Option<Video>, TwitchCtlError> {
+        match self
+            .helix_data::<Vec<Video>, _>(
+                Operation::GetVideos,
+                Method::GET,
+                "videos",
+                &[("id", id.to_string())],
+                NO_BODY,
+            )
+            .await
+        {
+            Ok(res) => Ok(res.data.into_iter().next()),
+            Err(TwitchCtlError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Deletes videos of the token user, returns the ids of the deleted videos.
+    ///
+    /// Nothing is deleted in a dry run, so the result is empty.
+    pub async fn delete_videos(
+        &self,
+        video_ids: &[&VideoId],
+    ) -> Result<Vec<VideoId>, TwitchCtlError> {
+        let ids: Vec<_> = video_ids.iter().map(|id| id.to_string()).collect();
+        self.confirmed(&format!("Delete the videos {}?", ids.join(", ")))?;
+        let mut deleted = vec![];
+        // Twitch accepts up to 5 ids per request
+        for video_ids in video_ids.chunks(5) {
+            let query: Vec<_> = video_ids.iter().map(|id| ("id", id.to_string())).collect();
+            if let Some(mut res) = self
+                .helix_write::<Vec<VideoId>, _>(
+                    Operation::DeleteVideos,
+                    Method::DELETE,
+                    "videos",
+                    &query,
+                    NO_BODY,
+                )
+                .await?
+            {
+                deleted.append(&mut res.data);
+            }
+        }
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::api::{ApiClient, BlockContext, BlockReason, UserIdent};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::UserName;
+
+#[derive(Debug, StructOpt)]
+/// manage the block list of the token user
+pub struct BlocksOptions {
+    #[structopt(subcommand)]
+    pub subcommand: BlocksSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum BlocksSubcommand {
+    /// lists the blocked users
+    List,
+    /// blocks a user
+    Add {
+        /// the name of the user
+        user: UserName,
+        /// where the user annoyed you
+        ///
+        /// (supported values: chat, whisper)
+        #[structopt(short, long)]
+        context: Option<BlockContext>,
+        /// why the user is blocked
+        ///
+        /// (supported values: spam, harassment, other)
+        #[structopt(short, long)]
+        reason: Option<BlockReason>,
+    },
+    /// unblocks a user
+    Remove {
+        /// the name of the user
+        user: UserName,
+    },
+}
+
+pub async fn blocks(client: ApiClient<'_>, command: BlocksSubcommand) {
+    match command {
+        BlocksSubcommand::List => match client.get_blocked_users().await {
+            Ok(users) => {
+                for user in users {
+                    println!("{} {}", user.user_id, user.user_login);
+                }
+            }
+            Err(e) => exit!(
+                1,
+                "An error occurred while fetching the blocked users: {}",
+                e
+            ),
+        },
+        BlocksSubcommand::Add {
+            user,
+            context,
+            reason,
+        } => {
+            if let Err(e) = client
+                .block_user(UserIdent::UserName(user.into()), context, reason)
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        BlocksSubcommand::Remove { user } => {
+            if let Err(e) = client.unblock_user(UserIdent::UserName(user.into())).await {
+                exit!(1, "{}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::api::{
+    get_broadcaster_id_or_die, ApiClient, ChannelFollowers, ChannelSubscriptions, UserIdent,
+};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{UserId, UserName};
+
+#[derive(Debug, StructOpt)]
+/// show or manipulate a channel
+pub struct ChannelOptions {
+    #[structopt(subcommand)]
+    pub subcommand: ChannelSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum ChannelSubcommand {
+    /// show the current title, category and language of a broadcaster
+    Info {
+        /// the name of the broadcaster for which to show the information
+        ///
+        /// if omitted the token user is used
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// show whether a broadcaster is live
+    Status {
+        /// the name of the broadcaster for which to show the status
+        ///
+        /// if omitted the token user is used
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// list the followers of a broadcaster
+    Followers {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// list all followers instead of the 100 most recent ones
+        #[structopt(short, long)]
+        all: bool,
+    },
+    /// show the progress of the goals of a broadcaster
+    Goals {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// show the current or most recent hype train of the token user
+    HypeTrain {
+        /// list all recorded hype trains
+        #[structopt(short, long)]
+        all: bool,
+    },
+    /// list the subscribers of the token user
+    Subscribers,
+    /// starts a commercial on the channel of the token user
+    Commercial {
+        /// the length in seconds
+        ///
+        /// (supported values: 30, 60, 90, 120, 150, 180)
+        #[structopt(default_value = "30")]
+        length: u32,
+    },
+    /// clips the last seconds of the stream of a broadcaster
+    Clip {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// start the clip a few seconds earlier to account for the stream delay
+        #[structopt(short, long)]
+        delay: bool,
+    },
+    /// raids another channel from the channel of the token user
+    Raid {
+        /// the name of the broadcaster to raid
+        target: UserName,
+    },
+    /// cancels the pending raid of the token user
+    Unraid,
+    /// creates a marker at the current position of the stream of the token user
+    Marker {
+        /// the description of the marker, at most 140 characters
+        description: Option<String>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn channel(client: ApiClient<'_>, command: ChannelSubcommand) {
+    match command {
+        ChannelSubcommand::Info {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_channel_information(&id).await {
+                Ok(info) => {
+                    println!("Title:    {}", info.title().unwrap_or_default());
+                    println!("Category: {}", info.game_name().unwrap_or_default());
+                    println!("Language: {}", info.language().unwrap_or_default());
+                }
+                Err(e) => exit!(
+                    1,
+                    "An error occurred while fetching the channel information: {}",
+                    e
+                ),
+            }
+        }
+        ChannelSubcommand::Status {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_stream(&id).await {
+                Ok(Some(stream)) => {
+                    println!("Live since {}", stream.started_at.as_str());
+                    println!("Title:    {}", stream.title);
+                    println!("Category: {}", stream.game_name);
+                    println!("Viewers:  {}", stream.viewer_count);
+                }
+                Ok(None) => println!("Offline"),
+                Err(e) => exit!(1, "An error occurred while fetching the stream: {}", e),
+            }
+        }
+        ChannelSubcommand::Followers {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            all,
+        } => {
+            let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_channel_followers(&id, all).await {
+                Ok(ChannelFollowers { total, followers }) => {
+                    println!("{} followers", total);
+                    for follower in followers {
+                        println!("{} {}", follower.followed_at, follower.user_name);
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while fetching the followers: {}", e),
+            }
+        }
+        ChannelSubcommand::Goals {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_creator_goals(&id).await {
+                Ok(goals) => {
+                    for goal in goals {
+                        println!(
+                            "{} {}/{} '{}'",
+                            goal.kind, goal.current_amount, goal.target_amount, goal.description
+                        );
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while fetching the goals: {}", e),
+            }
+        }
+        ChannelSubcommand::HypeTrain { all } => {
+            let id = get_broadcaster_id_or_die(&client, None, None).await;
+            match client.get_hype_train_events(&id, all).await {
+                Ok(trains) if trains.is_empty() => println!("No hype train yet"),
+                Ok(trains) => {
+                    for train in trains {
+                        println!(
+                            "Level {} {}/{} started at {}, expires at {}",
+                            train.level,
+                            train.total,
+                            train.goal,
+                            train.started_at,
+                            train.expires_at
+                        );
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while fetching the hype train: {}", e),
+            }
+        }
+        ChannelSubcommand::Subscribers => {
+            let id = get_broadcaster_id_or_die(&client, None, None).await;
+            match client.get_subscriptions(&id).await {
+                Ok(ChannelSubscriptions {
+                    total,
+                    points,
+                    subscribers,
+                }) => {
+                    println!("{} subscribers, {} points", total, points);
+                    for subscriber in subscribers {
+                        let tier = subscriber.tier.trim_end_matches("000");
+                        if subscriber.is_gift {
+                            println!(
+                                "tier {} {} (gifted by {})",
+                                tier, subscriber.user_name, subscriber.gifter_name
+                            );
+                        } else {
+                            println!("tier {} {}", tier, subscriber.user_name);
+                        }
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while fetching the subscribers: {}", e),
+            }
+        }
+        ChannelSubcommand::Commercial { length } => {
+            let id = get_broadcaster_id_or_die(&client, None, None).await;
+            match client.start_commercial(&id, length).await {
+                Ok(Some(commercial)) => println!(
+                    "Started a {}s commercial, the next one can be started in {}s",
+                    commercial.length, commercial.retry_after
+                ),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ChannelSubcommand::Clip {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            delay,
+        } => {
+            let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.create_clip(&id, delay).await {
+                Ok(Some(clip)) => {
+                    println!("Created clip `{}`, edit it at {}", clip.id, clip.edit_url)
+                }
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ChannelSubcommand::Raid { target } => {
+            let id = get_broadcaster_id_or_die(&client, None, None).await;
+            match client
+                .start_raid(&id, UserIdent::UserName(target.into()))
+                .await
+            {
+                Ok(Some(raid)) if raid.is_mature => println!("Started raid to a mature channel"),
+                Ok(Some(_)) => println!("Started raid"),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ChannelSubcommand::Unraid => {
+            let id = get_broadcaster_id_or_die(&client, None, None).await;
+            if let Err(e) = client.cancel_raid(&id).await {
+                exit!(1, "{}", e);
+            }
+        }
+        ChannelSubcommand::Marker { description } => {
+            let id = get_broadcaster_id_or_die(&client, None, None).await;
+            match client.create_stream_marker(&id, description).await {
+                Ok(Some(marker)) => println!(
+                    "Created marker `{}` at {}s",
+                    marker.id, marker.position_seconds
+                ),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+    }
+}
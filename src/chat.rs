@@ -0,0 +1,336 @@
+use crate::api::{
+    get_broadcaster_id_or_die, AnnouncementColor, ApiClient, ChatSettingsPatch, UserIdent,
+};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{UserId, UserName};
+
+#[derive(Debug, StructOpt)]
+/// manage a broadcasters chat
+pub struct ChatOptions {
+    #[structopt(subcommand)]
+    pub subcommand: ChatSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum ChatSubcommand {
+    /// sends an announcement to the chat
+    Announce {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the color of the announcement
+        ///
+        /// (supported values: blue, green, orange, purple, primary)
+        #[structopt(short, long, default_value = "primary")]
+        color: AnnouncementColor,
+        /// the message to announce
+        message: String,
+    },
+    /// gives a shoutout to another broadcaster
+    Shoutout {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the name of the broadcaster to shout out
+        target: UserName,
+    },
+    /// shows or changes the chat modes
+    ///
+    /// modes that are not given are left unchanged
+    Settings {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// limit how often users can send messages
+        #[structopt(long)]
+        slow: Option<bool>,
+        /// seconds users have to wait between messages, between 3 and 120
+        #[structopt(long)]
+        slow_wait: Option<u32>,
+        /// only allow followers to chat
+        #[structopt(long)]
+        followers_only: Option<bool>,
+        /// minutes users have to follow before they can chat
+        #[structopt(long)]
+        follow_duration: Option<u32>,
+        /// only allow subscribers to chat
+        #[structopt(long)]
+        subscribers_only: Option<bool>,
+        /// only allow messages containing just emotes
+        #[structopt(long)]
+        emote_only: Option<bool>,
+        /// reject messages that were already sent recently
+        #[structopt(long)]
+        unique_chat: Option<bool>,
+    },
+    /// whispers a message to a user
+    Whisper {
+        /// the name of the user
+        user: UserName,
+        /// the message to send
+        message: String,
+        /// the first whisper to the user, which Twitch limits to 500 characters
+        #[structopt(long)]
+        new: bool,
+    },
+    /// checks whether AutoMod would hold back messages
+    Check {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the messages to check
+        #[structopt(required = true)]
+        messages: Vec<String>,
+    },
+    /// removes a single message from the chat
+    Delete {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the id of the message
+        message_id: String,
+    },
+    /// removes all messages from the chat
+    Clear {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// shows or changes whether shield mode is active
+    Shield {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// `true` to activate, `false` to deactivate shield mode
+        active: Option<bool>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn chat(client: ApiClient<'_>, command: ChatSubcommand) {
+    match command {
+        ChatSubcommand::Announce {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            color,
+            message,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client
+                .send_announcement(&broadcaster_id, &message, color)
+                .await
+            {
+                Ok(_) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ChatSubcommand::Shoutout {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            target,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client
+                .send_shoutout(&broadcaster_id, UserIdent::UserName(target.into()))
+                .await
+            {
+                Ok(_) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ChatSubcommand::Settings {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            slow,
+            slow_wait,
+            followers_only,
+            follow_duration,
+            subscribers_only,
+            emote_only,
+            unique_chat,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            let patch = ChatSettingsPatch {
+                slow_mode: slow,
+                slow_mode_wait_time: slow_wait,
+                follower_mode: followers_only,
+                follower_mode_duration: follow_duration,
+                subscriber_mode: subscribers_only,
+                emote_mode: emote_only,
+                unique_chat_mode: unique_chat,
+            };
+            let changed = slow.is_some()
+                || slow_wait.is_some()
+                || followers_only.is_some()
+                || follow_duration.is_some()
+                || subscribers_only.is_some()
+                || emote_only.is_some()
+                || unique_chat.is_some();
+            if changed {
+                if let Err(e) = client.update_chat_settings(&broadcaster_id, patch).await {
+                    exit!(1, "{}", e);
+                }
+            }
+            match client.get_chat_settings(&broadcaster_id).await {
+                Ok(settings) => {
+                    println!(
+                        "Slow mode:        {}",
+                        on_off(settings.slow_mode, settings.slow_mode_wait_time, "s")
+                    );
+                    println!(
+                        "Followers only:   {}",
+                        on_off(
+                            settings.follower_mode,
+                            settings.follower_mode_duration,
+                            "min"
+                        )
+                    );
+                    println!(
+                        "Subscribers only: {}",
+                        on_off(settings.subscriber_mode, None, "")
+                    );
+                    println!(
+                        "Emote only:       {}",
+                        on_off(settings.emote_mode, None, "")
+                    );
+                    println!(
+                        "Unique chat:      {}",
+                        on_off(settings.unique_chat_mode, None, "")
+                    );
+                }
+                Err(e) => exit!(
+                    1,
+                    "An error occurred while fetching the chat settings: {}",
+                    e
+                ),
+            }
+        }
+        ChatSubcommand::Whisper { user, message, new } => {
+            if let Err(e) = client
+                .send_whisper(UserIdent::UserName(user.into()), &message, new)
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        ChatSubcommand::Check {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            messages,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            let checks = messages
+                .iter()
+                .enumerate()
+                .map(|(i, message)| (i.to_string(), message.clone()))
+                .collect();
+            match client.check_automod(&broadcaster_id, checks).await {
+                Ok(results) => {
+                    for result in results {
+                        let message = result
+                            .msg_id
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|i| messages.get(i));
+                        println!(
+                            "{} '{}'",
+                            if result.is_permitted {
+                                "permitted"
+                            } else {
+                                "held"
+                            },
+                            message.map_or("", String::as_str)
+                        );
+                    }
+                }
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ChatSubcommand::Delete {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            message_id,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client
+                .delete_chat_message(&broadcaster_id, &message_id)
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        ChatSubcommand::Clear {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client.clear_chat(&broadcaster_id).await {
+                exit!(1, "{}", e);
+            }
+        }
+        ChatSubcommand::Shield {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            active,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            let status = match active {
+                Some(active) => client.set_shield_mode(&broadcaster_id, active).await,
+                None => client
+                    .get_shield_mode_status(&broadcaster_id)
+                    .await
+                    .map(Some),
+            };
+            match status {
+                Ok(Some(status)) => println!(
+                    "Shield mode {}, last activated at {} by {}",
+                    on_off(status.is_active, None, ""),
+                    status.last_activated_at,
+                    status.moderator_name
+                ),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+    }
+}
+
+fn on_off(enabled: bool, value: Option<u32>, unit: &str) -> String {
+    match (enabled, value) {
+        (true, Some(value)) => format!("on ({}{})", value, unit),
+        (true, None) => "on".to_string(),
+        (false, _) => "off".to_string(),
+    }
+}
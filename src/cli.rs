@@ -1,16 +1,33 @@
 use std::{path::PathBuf, str::FromStr};
 
+use crate::api::CategoryMatch;
+use crate::blocks::{BlocksOptions, BlocksSubcommand};
+use crate::channel::{ChannelOptions, ChannelSubcommand};
+use crate::chat::ChatOptions;
+use crate::games::GamesOptions;
+use crate::moderation::ModerationOptions;
+use crate::polls::PollsOptions;
+use crate::predictions::PredictionsOptions;
 use crate::rewards::RewardsOptions;
-use crate::tags::TagsOptions;
+use crate::schedule::ScheduleOptions;
+use crate::tags::{TagsOptions, TagsSubcommand};
+use crate::videos::{VideosOptions, VideosSubcommand};
 use structopt::{
     clap::{AppSettings, Shell},
     StructOpt,
 };
+use twitch_api2::twitch_oauth2::Scope;
 
 /// A sane Twitch commandline interface
 #[derive(Debug, StructOpt)]
 #[structopt(name = "twitchctl", global_settings = &[AppSettings::DeriveDisplayOrder])]
 pub struct CliOptions {
+    /// read the oauth token from this file instead of `TWITCHCTL_TOKEN`
+    #[structopt(long, global = true)]
+    pub token_file: Option<PathBuf>,
+    /// only print the changes that would be sent to Twitch
+    #[structopt(long, global = true)]
+    pub dry_run: bool,
     #[structopt(subcommand)]
     pub category: Category,
 }
@@ -27,6 +44,14 @@ pub enum Category {
         /// max amount of results to show
         #[structopt(short, long, default_value = "20")]
         max_results: usize,
+        /// how category names are matched against the search term
+        ///
+        /// (supported values: contains, starts-with, exact)
+        #[structopt(long, default_value = "contains")]
+        match_mode: CategoryMatch,
+        /// print the categories as JSON
+        #[structopt(long)]
+        json: bool,
         /// the category in which to search
         category: String,
     },
@@ -80,6 +105,115 @@ pub enum Category {
         #[structopt(flatten)]
         options: RewardsOptions,
     },
+    /// shows or manages channel information
+    Channel {
+        #[structopt(flatten)]
+        options: ChannelOptions,
+    },
+    /// manages the chat
+    Chat {
+        #[structopt(flatten)]
+        options: ChatOptions,
+    },
+    /// lists popular games or looks up games
+    Games {
+        #[structopt(flatten)]
+        options: GamesOptions,
+    },
+    /// moderates a channel
+    Moderation {
+        #[structopt(flatten)]
+        options: ModerationOptions,
+    },
+    /// shows or changes the stream schedule
+    Schedule {
+        #[structopt(flatten)]
+        options: ScheduleOptions,
+    },
+    /// creates or manages polls
+    Poll {
+        #[structopt(flatten)]
+        options: PollsOptions,
+    },
+    /// creates or manages predictions
+    Prediction {
+        #[structopt(flatten)]
+        options: PredictionsOptions,
+    },
+    /// lists or deletes videos
+    Videos {
+        #[structopt(flatten)]
+        options: VideosOptions,
+    },
+    /// manages the block list of the token user
+    Blocks {
+        #[structopt(flatten)]
+        options: BlocksOptions,
+    },
+}
+
+impl Category {
+    /// The scopes the token needs for this command
+    pub fn required_scopes(&self) -> &'static [Scope] {
+        match self {
+            Category::Tags {
+                options:
+                    TagsOptions {
+                        subcommand: TagsSubcommand::Set { .. },
+                        ..
+                    },
+            } => &[Scope::ChannelManageBroadcast],
+            Category::File { .. } | Category::Preset { .. } => &[
+                Scope::ChannelManageBroadcast,
+                Scope::ChannelManageRedemptions,
+            ],
+            Category::Reward { .. } => &[Scope::ChannelManageRedemptions],
+            Category::Channel {
+                options:
+                    ChannelOptions {
+                        subcommand: ChannelSubcommand::Commercial { .. },
+                    },
+            } => &[Scope::ChannelEditCommercial],
+            Category::Channel {
+                options:
+                    ChannelOptions {
+                        subcommand: ChannelSubcommand::Marker { .. },
+                    },
+            } => &[Scope::ChannelManageBroadcast],
+            Category::Channel {
+                options:
+                    ChannelOptions {
+                        subcommand: ChannelSubcommand::Clip { .. },
+                    },
+            } => &[Scope::ClipsEdit],
+            Category::Channel {
+                options:
+                    ChannelOptions {
+                        subcommand: ChannelSubcommand::Subscribers,
+                    },
+            } => &[Scope::ChannelReadSubscriptions],
+            Category::Channel {
+                options:
+                    ChannelOptions {
+                        subcommand: ChannelSubcommand::HypeTrain { .. },
+                    },
+            } => &[Scope::ChannelReadHypeTrain],
+            Category::Videos {
+                options:
+                    VideosOptions {
+                        subcommand: VideosSubcommand::Delete { .. },
+                    },
+            } => &[Scope::ChannelManageVideos],
+            Category::Blocks {
+                options:
+                    BlocksOptions {
+                        subcommand: BlocksSubcommand::List,
+                    },
+            } => &[Scope::UserReadBlockedUsers],
+            Category::Blocks { .. } => &[Scope::UserManageBlockedUsers],
+            _ => &[],
+        }
+    }
 }
 
 #[derive(Debug)]
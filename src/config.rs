@@ -1,19 +1,94 @@
 use dotenv;
+use std::{fs, path::PathBuf};
+
+use crate::{
+    api::{TokenRefresh, TokenSource},
+    warning,
+};
 
 const TOKEN_VAR: &str = "TWITCHCTL_TOKEN";
+const REFRESH_TOKEN_VAR: &str = "TWITCHCTL_REFRESH_TOKEN";
+const CLIENT_ID_VAR: &str = "TWITCHCTL_CLIENT_ID";
+const CLIENT_SECRET_VAR: &str = "TWITCHCTL_CLIENT_SECRET";
 
 pub struct DotEnv {
-    pub token: String,
+    pub token: TokenSource,
+    pub refresh: Option<TokenRefresh>,
+}
+
+/// Loads the token from `token_file` if set, and from `TWITCHCTL_TOKEN` otherwise
+pub fn load_env(token_file: Option<PathBuf>) -> DotEnv {
+    let token = match token_file {
+        Some(file) => TokenSource::File(file),
+        None => TokenSource::EnvVar(TOKEN_VAR.to_string()),
+    };
+
+    let refresh = match (
+        dotenv::var(REFRESH_TOKEN_VAR),
+        dotenv::var(CLIENT_ID_VAR),
+        dotenv::var(CLIENT_SECRET_VAR),
+    ) {
+        (Ok(refresh_token), Ok(client_id), Ok(client_secret)) => Some(TokenRefresh {
+            refresh_token,
+            client_id,
+            client_secret,
+            on_refresh: match &token {
+                TokenSource::File(file) => {
+                    let file = file.clone();
+                    Box::new(move |token: &str, refresh_token: &str| {
+                        store_token_file(&file, token);
+                        store_env(&[(REFRESH_TOKEN_VAR, refresh_token)]);
+                    })
+                }
+                _ => Box::new(|token: &str, refresh_token: &str| {
+                    store_env(&[(TOKEN_VAR, token), (REFRESH_TOKEN_VAR, refresh_token)])
+                }),
+            },
+        }),
+        _ => None,
+    };
+
+    DotEnv { token, refresh }
 }
 
-pub fn load_env() -> DotEnv {
-    let token = match dotenv::var(TOKEN_VAR) {
-        Ok(token) => token,
-        Err(_) => panic!(
-            "Your Twitch oauth token is missing!\r\n{} should exist in .env or your env vars.",
-            TOKEN_VAR
-        ),
+/// Writes the refreshed tokens in `vars` back to the .env file
+fn store_env(vars: &[(&str, &str)]) {
+    let names: Vec<_> = vars.iter().map(|(name, _)| *name).collect();
+    let path = match dotenv::dotenv() {
+        Ok(path) => path,
+        Err(_) => {
+            warning!(
+                "Your token was refreshed, but there is no .env file to store it in. \
+                Update {} to keep using it.",
+                names.join(" and ")
+            );
+            return;
+        }
     };
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let prefixes: Vec<_> = names.iter().map(|name| format!("{}=", name)).collect();
+    let mut lines: Vec<String> = content
+        .lines()
+        .filter(|line| !prefixes.iter().any(|prefix| line.starts_with(prefix)))
+        .map(String::from)
+        .collect();
+    for (name, value) in vars {
+        lines.push(format!("{}={}", name, value));
+    }
+    if fs::write(&path, lines.join("\n") + "\n").is_err() {
+        warning!(
+            "Your token was refreshed, but it could not be written to `{}`",
+            path.display()
+        );
+    }
+}
 
-    DotEnv { token }
+/// Writes a refreshed token back to the token file
+fn store_token_file(file: &PathBuf, token: &str) {
+    if fs::write(file, format!("{}\n", token)).is_err() {
+        warning!(
+            "Your token was refreshed, but it could not be written to `{}`",
+            file.display()
+        );
+    }
 }
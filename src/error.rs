@@ -0,0 +1,232 @@
+use std::{error::Error, time::Duration};
+use twitch_api2::{
+    helix::{
+        ClientRequestError, HelixRequestDeleteError, HelixRequestGetError, HelixRequestPatchError,
+        HelixRequestPostError, HelixRequestPutError,
+    },
+    twitch_oauth2::{RefreshTokenError, Scope, ValidationError},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum TwitchCtlError {
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("Rate limit exceeded{}", retry_hint(.retry_after))]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("Request to Twitch failed: {0}")]
+    Helix(#[source] Box<dyn Error + Send + Sync>),
+    #[error("{0}")]
+    Validation(String),
+    #[error("EventSub connection failed: {0}")]
+    EventSub(#[source] Box<dyn Error + Send + Sync>),
+    /// Twitch could not tell whether the token is valid
+    #[error("Could not validate the token: {0}")]
+    ValidationUnavailable(#[source] Box<dyn Error + Send + Sync>),
+    #[error("Your Twitch oauth token is missing!\r\n{0}")]
+    MissingToken(String),
+    #[error("The token is missing the scopes: {}", scope_list(.0))]
+    MissingScopes(Vec<Scope>),
+    /// the action is only possible while streaming
+    #[error("You must be streaming to {0}.")]
+    NotLive(&'static str),
+    #[error("Shoutout is on cooldown{}", retry_hint(.retry_after))]
+    ShoutoutCooldown { retry_after: Option<Duration> },
+    #[error("You need a verified phone number to send whispers.")]
+    WhisperPhoneNotVerified,
+    #[error("Too many whispers sent{}", retry_hint(.retry_after))]
+    WhisperRateLimited { retry_after: Option<Duration> },
+    /// the id of the user
+    #[error("`{0}` is already a moderator.")]
+    AlreadyModerator(String),
+    /// the id of the user
+    #[error("`{0}` is not a moderator.")]
+    NotModerator(String),
+    #[error("All VIP slots of the channel are taken.")]
+    VipLimitReached,
+    #[error("You can not raid your own channel.")]
+    RaidSelf,
+    /// the id of the channel that is not live
+    #[error("The channel `{0}` is not live.")]
+    RaidTargetOffline(String),
+    #[error("This requires a user access token, the client uses an app access token.")]
+    UserTokenRequired,
+    #[error("Twitch did not respond within {}s.", .0.as_secs())]
+    Timeout(Duration),
+    /// the titles of the matching rewards
+    #[error("Several rewards match, did you mean {}?", title_list(.0))]
+    AmbiguousReward(Vec<String>),
+    #[error("Only partners can set a stream delay.")]
+    DelayNotAllowed,
+    /// the confirmation hook of the client declined the action
+    #[error("Cancelled.")]
+    Cancelled,
+    /// the request was only logged, so there is no response
+    #[error("Nothing was sent to Twitch in a dry run.")]
+    DryRun,
+}
+
+/// An error response of a Helix endpoint
+#[derive(thiserror::Error, Debug)]
+#[error("{message} ({status})")]
+pub struct HelixResponseError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl TwitchCtlError {
+    pub(crate) fn from_response(status: u16, message: String) -> Self {
+        match status {
+            401 => TwitchCtlError::Auth(message),
+            404 => TwitchCtlError::NotFound(message),
+            429 if message.contains("Shoutout") => TwitchCtlError::ShoutoutCooldown {
+                retry_after: shoutout_cooldown(&message),
+            },
+            429 => TwitchCtlError::RateLimited { retry_after: None },
+            _ => TwitchCtlError::Helix(Box::new(HelixResponseError { status, message })),
+        }
+    }
+
+    /// Returns the status code and message when Twitch rejected the request
+    pub fn helix_response(&self) -> Option<&HelixResponseError> {
+        match self {
+            TwitchCtlError::Helix(error) => error.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+fn retry_hint(retry_after: &Option<Duration>) -> String {
+    match retry_after {
+        Some(retry_after) => format!(", retry after {}s", retry_after.as_secs()),
+        None => String::new(),
+    }
+}
+
+/// Twitch only tells which of the shoutout cooldowns applies in the message
+fn shoutout_cooldown(message: &str) -> Option<Duration> {
+    if message.contains("60 minutes") {
+        Some(Duration::from_secs(60 * 60))
+    } else if message.contains("2 minutes") {
+        Some(Duration::from_secs(2 * 60))
+    } else {
+        None
+    }
+}
+
+fn title_list(titles: &[String]) -> String {
+    titles
+        .iter()
+        .map(|title| format!("`{}`", title))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn scope_list(scopes: &[Scope]) -> String {
+    scopes
+        .iter()
+        .map(|scope| scope.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the status code and message of an error response from Helix
+fn helix_status<RE: Error + Send + Sync + 'static>(
+    error: &ClientRequestError<RE>,
+) -> Option<(u16, &str)> {
+    match error {
+        ClientRequestError::HelixRequestGetError(HelixRequestGetError::Error {
+            status,
+            message,
+            ..
+        })
+        | ClientRequestError::HelixRequestPutError(HelixRequestPutError::Error {
+            status,
+            message,
+            ..
+        })
+        | ClientRequestError::HelixRequestPostError(HelixRequestPostError::Error {
+            status,
+            message,
+            ..
+        })
+        | ClientRequestError::HelixRequestPatchError(HelixRequestPatchError::Error {
+            status,
+            message,
+            ..
+        })
+        | ClientRequestError::HelixRequestDeleteError(HelixRequestDeleteError::Error {
+            status,
+            message,
+            ..
+        }) => Some((status.as_u16(), message.as_str())),
+        _ => None,
+    }
+}
+
+impl<RE: Error + Send + Sync + 'static> From<ClientRequestError<RE>> for TwitchCtlError {
+    fn from(error: ClientRequestError<RE>) -> Self {
+        match helix_status(&error) {
+            Some((status, message)) => TwitchCtlError::from_response(status, message.to_string()),
+            None => TwitchCtlError::Helix(Box::new(error)),
+        }
+    }
+}
+
+impl<RE: Error + Send + Sync + 'static> From<ValidationError<RE>> for TwitchCtlError {
+    fn from(error: ValidationError<RE>) -> Self {
+        match error {
+            ValidationError::NotAuthorized => TwitchCtlError::Auth(error.to_string()),
+            error => TwitchCtlError::ValidationUnavailable(Box::new(error)),
+        }
+    }
+}
+
+impl<RE: Error + Send + Sync + 'static> From<RefreshTokenError<RE>> for TwitchCtlError {
+    fn from(error: RefreshTokenError<RE>) -> Self {
+        TwitchCtlError::Auth(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_found_and_auth_statuses() {
+        assert!(matches!(
+            TwitchCtlError::from_response(404, "Not Found".to_string()),
+            TwitchCtlError::NotFound(message) if message == "Not Found"
+        ));
+        assert!(matches!(
+            TwitchCtlError::from_response(401, "Invalid OAuth token".to_string()),
+            TwitchCtlError::Auth(message) if message == "Invalid OAuth token"
+        ));
+    }
+
+    #[test]
+    fn too_many_requests() {
+        assert!(matches!(
+            TwitchCtlError::from_response(429, "Too Many Requests".to_string()),
+            TwitchCtlError::RateLimited { retry_after: None }
+        ));
+        assert!(matches!(
+            TwitchCtlError::from_response(
+                429,
+                "The broadcaster may not give the same streamer a Shoutout within 60 minutes."
+                    .to_string()
+            ),
+            TwitchCtlError::ShoutoutCooldown { retry_after: Some(retry_after) }
+                if retry_after == Duration::from_secs(60 * 60)
+        ));
+    }
+
+    #[test]
+    fn other_statuses_keep_the_response() {
+        let error = TwitchCtlError::from_response(400, "Missing broadcaster_id".to_string());
+        let response = error.helix_response().expect("a Helix response error");
+        assert_eq!(response.status, 400);
+        assert_eq!(response.message, "Missing broadcaster_id");
+    }
+}
@@ -52,28 +52,20 @@ pub async fn handle_file(
     let config: Config = fig
         .extract()
         .unwrap_or_else(|e| exit!(1, "Failed to parse configuration: {:?}", e));
+    let user_id = client.get_user_id()?;
     // To not move config struct
     let tags = config.tags;
     let locale = config.config_locale;
 
     let tag_rq = async {
         if let Some(tags) = tags {
-            client
-                .replace_stream_tags(
-                    client.get_user_id(),
-                    client
-                        .get_tag_ids_matching(
-                            tags.as_slice(),
-                            match locale.as_ref() {
-                                Some(locale) => locale,
-                                None => "en-us",
-                            },
-                        )
-                        .await
-                        .unwrap_or_else(|e| exit!(1, "Failed to request tags: {:?}", e)),
-                )
+            let unmatched = client
+                .set_stream_tags_by_name(user_id, tags.as_slice(), locale.as_deref())
                 .await
                 .unwrap_or_else(|e| exit!(1, "Failed to set tags: {:?}", e));
+            if !unmatched.is_empty() {
+                warning!("Could not find the tags `{}`", unmatched.join("`, `"));
+            }
         }
     };
     if config.language.is_some() || config.title.is_some() || config.category.is_some() {
@@ -85,17 +77,36 @@ pub async fn handle_file(
             builder.title(title);
         }
         if let Some(category) = config.category {
-            builder.category(
-                client
-                    .search_category(&category)
-                    .await
-                    .unwrap_or_else(|e| exit!(1, "Failed to request category: {:?}", e))
-                    .unwrap_or_else(|| exit!(1, "Could not find a category for `{}`", category))
-                    .id,
-            );
+            let found = client
+                .search_category(&category)
+                .await
+                .unwrap_or_else(|e| exit!(1, "Failed to request category: {:?}", e));
+            match found {
+                Some(found) => {
+                    builder.category(found.id);
+                }
+                None => {
+                    let suggestions: Vec<_> = client
+                        .suggest_categories(&category, 3)
+                        .await
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|suggestion| suggestion.name)
+                        .collect();
+                    if suggestions.is_empty() {
+                        exit!(1, "Could not find a category for `{}`", category)
+                    }
+                    exit!(
+                        1,
+                        "Could not find a category for `{}`, did you mean `{}`?",
+                        category,
+                        suggestions.join("`, `")
+                    )
+                }
+            }
         }
         client
-            .modify_channel_information(client.get_user_id(), builder.build().unwrap())
+            .modify_channel_information(user_id, builder.build().unwrap())
             .await
             .unwrap_or_else(|e| exit!(1, "Failed to set channel information: {:?}", e))
     }
@@ -104,12 +115,12 @@ pub async fn handle_file(
     }
     if let Some(rewards) = config.rewards {
         client
-            .get_rewards(client.get_user_id())
+            .get_manageable_rewards(user_id)
             .await?
             .iter()
             .map(|CustomReward { id, .. }| {
                 client.update_custom_reward(
-                    client.get_user_id(),
+                    user_id,
                     id,
                     UpdateCustomRewardBody::builder().is_enabled(false).build(),
                 )
@@ -119,14 +130,14 @@ pub async fn handle_file(
 
         rewards
             .iter()
-            .map(|title| client.find_reward(client.get_user_id(), title))
+            .map(|title| client.find_reward(user_id, title))
             .collect::<JoinAll<_>>()
             .await
             .iter()
             .filter_map(|r| {
                 if let Ok(Some(CustomReward { id, .. })) = r {
                     Some(client.update_custom_reward(
-                        client.get_user_id(),
+                        user_id,
                         id,
                         UpdateCustomRewardBody::builder().is_enabled(true).build(),
                     ))
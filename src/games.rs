@@ -0,0 +1,62 @@
+use crate::api::ApiClient;
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::{helix::games::Game, types::CategoryId};
+
+#[derive(Debug, StructOpt)]
+/// list popular games or look up games
+pub struct GamesOptions {
+    #[structopt(subcommand)]
+    pub subcommand: GamesSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum GamesSubcommand {
+    /// lists the games with the most viewers
+    Top {
+        /// max amount of games to show
+        #[structopt(short, long, default_value = "20")]
+        max_results: usize,
+    },
+    /// looks up games by id or exact name
+    Get {
+        /// the ids of the games
+        #[structopt(short, long)]
+        id: Vec<CategoryId>,
+        /// the names of the games
+        names: Vec<String>,
+    },
+}
+
+pub async fn games(client: ApiClient<'_>, command: GamesSubcommand) {
+    let res = match command {
+        GamesSubcommand::Top { max_results } => client.get_top_games(max_results).await,
+        GamesSubcommand::Get { id, names } => {
+            if id.is_empty() && names.is_empty() {
+                exit!(1, "Specify at least one game id or name");
+            }
+            client
+                .get_games(
+                    &id.iter().collect::<Vec<_>>(),
+                    &names.iter().map(String::as_str).collect::<Vec<_>>(),
+                )
+                .await
+        }
+    };
+    match res {
+        Ok(games) => list(&games),
+        Err(e) => exit!(1, "An error occurred while fetching the games: {}", e),
+    }
+}
+
+fn list(games: &[Game]) {
+    let max_len = games.iter().map(|game| game.id.as_str().len()).max();
+    for game in games {
+        println!(
+            "{:width$} {}",
+            game.id.as_str(),
+            game.name,
+            width = max_len.unwrap_or_default()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use serde::Serialize;
+use twitch_api2::helix::{points::CustomReward, search::search_categories::Category, users::User};
+
+use crate::exit;
+
+/// A category as printed with `--json`
+#[derive(Serialize, Debug)]
+pub struct CategoryDto {
+    pub id: String,
+    pub name: String,
+    pub box_art_url: String,
+}
+
+impl From<&Category> for CategoryDto {
+    fn from(category: &Category) -> Self {
+        CategoryDto {
+            id: category.id.to_string(),
+            name: category.name.clone(),
+            box_art_url: category.box_art_url.clone(),
+        }
+    }
+}
+
+/// A custom reward as printed with `--json`
+///
+/// The limits are `None` when disabled.
+#[derive(Serialize, Debug)]
+pub struct RewardDto {
+    pub id: String,
+    pub title: String,
+    pub prompt: String,
+    pub cost: u64,
+    pub background_color: String,
+    pub is_enabled: bool,
+    pub is_paused: bool,
+    pub is_user_input_required: bool,
+    pub should_redemptions_skip_request_queue: bool,
+    pub max_per_stream: Option<u64>,
+    pub max_per_user_per_stream: Option<u64>,
+    pub global_cooldown_seconds: Option<u64>,
+}
+
+impl From<&CustomReward> for RewardDto {
+    fn from(reward: &CustomReward) -> Self {
+        let max_per_stream = &reward.max_per_stream_setting;
+        let max_per_user = &reward.max_per_user_per_stream_setting;
+        let cooldown = &reward.global_cooldown_setting;
+        RewardDto {
+            id: reward.id.to_string(),
+            title: reward.title.clone(),
+            prompt: reward.prompt.clone(),
+            cost: reward.cost as u64,
+            background_color: reward.background_color.clone(),
+            is_enabled: reward.is_enabled,
+            is_paused: reward.is_paused,
+            is_user_input_required: reward.is_user_input_required,
+            should_redemptions_skip_request_queue: reward.should_redemptions_skip_request_queue,
+            max_per_stream: if max_per_stream.is_enabled {
+                Some(max_per_stream.max_per_stream as u64)
+            } else {
+                None
+            },
+            max_per_user_per_stream: if max_per_user.is_enabled {
+                Some(max_per_user.max_per_user_per_stream as u64)
+            } else {
+                None
+            },
+            global_cooldown_seconds: if cooldown.is_enabled {
+                Some(cooldown.global_cooldown_seconds as u64)
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// A user as printed with `--json`
+#[derive(Serialize, Debug)]
+pub struct UserDto {
+    pub id: String,
+    pub login: String,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub profile_image_url: Option<String>,
+}
+
+impl From<&User> for UserDto {
+    fn from(user: &User) -> Self {
+        UserDto {
+            id: user.id.to_string(),
+            login: user.login.to_string(),
+            display_name: user.display_name.to_string(),
+            description: user.description.clone(),
+            profile_image_url: user.profile_image_url.clone(),
+        }
+    }
+}
+
+/// Prints `value` as pretty printed JSON
+pub fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => exit!(1, "Failed to serialize output: {}", e),
+    }
+}
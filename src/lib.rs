@@ -0,0 +1,30 @@
+//! The Twitch API client behind twitchctl.
+//!
+//! [`api::ApiClient`] talks to Twitch, [`api::TwitchApi`] is implemented by it so code using
+//! the client can be written against the trait. To test such code without talking to Twitch
+//! enable the `test-util` feature, which adds `api::MockApiClient`:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! twitchctl = { git = "https://github.com/togglebyte/twitchctl", features = ["test-util"] }
+//! ```
+//!
+//! Requests are sent with `surf`. To send them with `reqwest` instead, e.g. to not build a second
+//! HTTP client into an application already using it, replace the default `surf` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! twitchctl = { git = "https://github.com/togglebyte/twitchctl", default-features = false, features = ["reqwest"] }
+//! ```
+
+#[cfg(feature = "reqwest")]
+extern crate reqwest_crate as reqwest;
+#[cfg(feature = "surf")]
+extern crate surf_crate as surf;
+
+mod macros;
+
+use macros::{exit, warning};
+
+pub mod api;
+pub mod error;
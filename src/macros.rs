@@ -1,12 +1,13 @@
-#[macro_export]
 macro_rules! exit {
     ($ec:expr, $($message:expr), +) => {
         {eprintln!($($message), +);
         std::process::exit($ec);}
     };
 }
+pub(crate) use exit;
 
-#[macro_export]
+// only the binary compares arguments, the library does not use it
+#[allow(unused_macros)]
 macro_rules! matches_any {
     ($value:expr, $first:expr) => {
         $value.eq_ignore_ascii_case($first)
@@ -15,10 +16,12 @@ macro_rules! matches_any {
         $value.eq_ignore_ascii_case($first) || matches_any!($value, $($pattern), +)
     };
 }
+#[allow(unused_imports)]
+pub(crate) use matches_any;
 
-#[macro_export]
 macro_rules! warning {
     ($($message:expr), +) => {
         eprintln!("[WARNING] {}", format!($($message), +));
     };
 }
+pub(crate) use warning;
@@ -1,27 +1,52 @@
 use structopt::StructOpt;
 
-mod api;
+mod blocks;
+mod channel;
+mod chat;
 mod cli;
 mod config;
 mod file;
+mod games;
+mod json;
+mod moderation;
+mod polls;
+mod predictions;
 mod preset;
 mod rewards;
+mod schedule;
 mod tags;
+mod videos;
 
-#[macro_use]
 mod macros;
 
+use twitchctl::api;
+
 use api::ApiClient;
+use blocks::blocks;
+use channel::channel;
+use chat::chat;
 use cli::{Category, CliOptions};
 use config::load_env;
 use file::handle_file;
+use games::games;
+use json::{print_json, CategoryDto};
+use macros::{exit, matches_any, warning};
+use moderation::moderation;
+use polls::polls;
+use predictions::predictions;
 use preset::handle_preset;
 use tags::tags;
 use rewards::rewards;
+use schedule::schedule;
+use videos::videos;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let CliOptions { category } = CliOptions::from_args();
+    let CliOptions {
+        token_file,
+        dry_run,
+        category,
+    } = CliOptions::from_args();
 
     if let Category::Completions { shell, target_dir } = &category {
         if !target_dir.exists() {
@@ -37,19 +62,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // check token after cli and completions are done
     // otherwise the tool crashes when you try to call it with -h
-    let env = load_env();
-    let client = ApiClient::new(&env.token).await?;
+    let env = load_env(token_file);
+    let client = ApiClient::from_source(env.token, env.refresh, category.required_scopes())
+        .await?
+        .with_dry_run(dry_run);
 
     match category {
         Category::Tags { options } => tags(client, &options.locale, options.subcommand).await,
         Category::Search {
             category,
             max_results,
+            match_mode,
+            json,
         } => {
-            println!(
-                "{:?}",
-                client.search_categories(&category, max_results).await?
-            );
+            let categories = client
+                .search_categories(&category, max_results, match_mode)
+                .await?;
+            if json {
+                print_json(
+                    &categories
+                        .unwrap_or_default()
+                        .iter()
+                        .map(CategoryDto::from)
+                        .collect::<Vec<_>>(),
+                );
+            } else {
+                println!("{:?}", categories);
+            }
         }
         Category::File { file, noenv } => handle_file(client, &file, noenv).await?,
         Category::Preset { query, noenv } => handle_preset(client, &query, noenv).await?,
@@ -57,6 +96,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             unreachable!("already handled above!")
         }
         Category::Reward { options } => rewards(client, options.subcommand).await,
+        Category::Channel { options } => channel(client, options.subcommand).await,
+        Category::Chat { options } => chat(client, options.subcommand).await,
+        Category::Games { options } => games(client, options.subcommand).await,
+        Category::Moderation { options } => moderation(client, options.subcommand).await,
+        Category::Schedule { options } => schedule(client, options.subcommand).await,
+        Category::Poll { options } => polls(client, options.subcommand).await,
+        Category::Prediction { options } => predictions(client, options.subcommand).await,
+        Category::Videos { options } => videos(client, options.subcommand).await,
+        Category::Blocks { options } => blocks(client, options.subcommand).await,
     }
 
     Ok(())
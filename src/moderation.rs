@@ -0,0 +1,242 @@
+use crate::api::{get_broadcaster_id_or_die, ApiClient, UserIdent};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{UserId, UserName};
+
+#[derive(Debug, StructOpt)]
+/// moderate a broadcasters channel
+pub struct ModerationOptions {
+    #[structopt(subcommand)]
+    pub subcommand: ModerationSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum ModerationSubcommand {
+    /// lists the terms AutoMod blocks
+    BlockedTerms {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// adds a term for AutoMod to block
+    BlockTerm {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the term, between 2 and 500 characters
+        text: String,
+    },
+    /// removes a blocked term
+    UnblockTerm {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the id of the term
+        id: String,
+    },
+    /// lists the VIPs of a channel
+    Vips {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// makes a user a VIP
+    AddVip {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the name of the user
+        user: UserName,
+    },
+    /// removes the VIP status of a user
+    RemoveVip {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the name of the user
+        user: UserName,
+    },
+    /// lists the moderators of a channel
+    Mods {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// makes a user a moderator
+    AddMod {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the name of the user
+        user: UserName,
+    },
+    /// removes the moderator status of a user
+    RemoveMod {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the name of the user
+        user: UserName,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn moderation(client: ApiClient<'_>, command: ModerationSubcommand) {
+    match command {
+        ModerationSubcommand::BlockedTerms {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_blocked_terms(&broadcaster_id).await {
+                Ok(terms) => {
+                    for term in terms {
+                        println!("{} '{}'", term.id, term.text);
+                    }
+                }
+                Err(e) => exit!(
+                    1,
+                    "An error occurred while fetching the blocked terms: {}",
+                    e
+                ),
+            }
+        }
+        ModerationSubcommand::BlockTerm {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            text,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.add_blocked_term(&broadcaster_id, &text).await {
+                Ok(Some(term)) => println!("Blocked term `{}`", term.id),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ModerationSubcommand::UnblockTerm {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            id,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client.remove_blocked_term(&broadcaster_id, &id).await {
+                exit!(1, "{}", e);
+            }
+        }
+        ModerationSubcommand::Vips {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_vips(&broadcaster_id).await {
+                Ok(vips) => list_users(&vips),
+                Err(e) => exit!(1, "An error occurred while fetching the VIPs: {}", e),
+            }
+        }
+        ModerationSubcommand::AddVip {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            user,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client
+                .add_vip(&broadcaster_id, UserIdent::UserName(user.into()))
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        ModerationSubcommand::RemoveVip {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            user,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client
+                .remove_vip(&broadcaster_id, UserIdent::UserName(user.into()))
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        ModerationSubcommand::Mods {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_moderators(&broadcaster_id).await {
+                Ok(moderators) => list_users(&moderators),
+                Err(e) => exit!(1, "An error occurred while fetching the moderators: {}", e),
+            }
+        }
+        ModerationSubcommand::AddMod {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            user,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client
+                .add_moderator(&broadcaster_id, UserIdent::UserName(user.into()))
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        ModerationSubcommand::RemoveMod {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            user,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            if let Err(e) = client
+                .remove_moderator(&broadcaster_id, UserIdent::UserName(user.into()))
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+    }
+}
+
+fn list_users(users: &[(UserId, String, String)]) {
+    for (id, _, name) in users {
+        println!("{} {}", id.as_str(), name);
+    }
+}
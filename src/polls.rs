@@ -0,0 +1,129 @@
+use crate::api::{get_broadcaster_id_or_die, ApiClient, Poll, PollEnd};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{UserId, UserName};
+
+#[derive(Debug, StructOpt)]
+/// run polls in a channel
+pub struct PollsOptions {
+    #[structopt(subcommand)]
+    pub subcommand: PollsSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum PollsSubcommand {
+    /// starts a new poll
+    Create {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the duration in seconds, between 15 and 1800
+        #[structopt(short, long, default_value = "60")]
+        duration: u32,
+        /// allow buying extra votes for this many channel points
+        #[structopt(short, long)]
+        channel_points: Option<u32>,
+        /// the question of the poll
+        title: String,
+        /// the 2 to 5 choices
+        #[structopt(required = true, min_values = 2, max_values = 5)]
+        choices: Vec<String>,
+    },
+    /// lists the polls of a broadcaster
+    List {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// ends a running poll
+    End {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// hide the results after ending the poll
+        #[structopt(short, long)]
+        archive: bool,
+        /// the id of the poll
+        id: String,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn polls(client: ApiClient<'_>, command: PollsSubcommand) {
+    match command {
+        PollsSubcommand::Create {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            duration,
+            channel_points,
+            title,
+            choices,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client
+                .create_poll(&broadcaster_id, &title, choices, duration, channel_points)
+                .await
+            {
+                Ok(Some(poll)) => println!("Started poll `{}`", poll.id),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        PollsSubcommand::List {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_polls(&broadcaster_id).await {
+                Ok(polls) => list(&polls),
+                Err(e) => exit!(1, "An error occurred while fetching the polls: {}", e),
+            }
+        }
+        PollsSubcommand::End {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            archive,
+            id,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            let status = if archive {
+                PollEnd::Archived
+            } else {
+                PollEnd::Terminated
+            };
+            match client.end_poll(&broadcaster_id, &id, status).await {
+                Ok(Some(poll)) => list(&[poll]),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+    }
+}
+
+fn list(polls: &[Poll]) {
+    for poll in polls {
+        println!("{} '{}' {}", poll.id, poll.title, poll.status);
+        for choice in &poll.choices {
+            println!("    {:>6} '{}'", choice.votes, choice.title);
+        }
+    }
+}
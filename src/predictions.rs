@@ -0,0 +1,204 @@
+use crate::api::{get_broadcaster_id_or_die, ApiClient, Prediction, PredictionStatus};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{UserId, UserName};
+
+#[derive(Debug, StructOpt)]
+/// run channel points predictions
+pub struct PredictionsOptions {
+    #[structopt(subcommand)]
+    pub subcommand: PredictionsSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum PredictionsSubcommand {
+    /// starts a new prediction
+    Create {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// how long viewers can predict, between 30 and 1800 seconds
+        #[structopt(short, long, default_value = "120")]
+        window: u32,
+        /// the title of the prediction
+        title: String,
+        /// the two possible outcomes
+        #[structopt(required = true, min_values = 2, max_values = 2)]
+        outcomes: Vec<String>,
+    },
+    /// lists the predictions of a broadcaster
+    List {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// stops viewers from making further predictions
+    Lock {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the id of the prediction
+        id: String,
+    },
+    /// pays out the points to the viewers that predicted the winning outcome
+    Resolve {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the id of the prediction
+        id: String,
+        /// the id of the winning outcome
+        outcome: String,
+    },
+    /// cancels a prediction and refunds the points
+    Cancel {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// the id of the prediction
+        id: String,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn predictions(client: ApiClient<'_>, command: PredictionsSubcommand) {
+    match command {
+        PredictionsSubcommand::Create {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            window,
+            title,
+            outcomes,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client
+                .create_prediction(&broadcaster_id, &title, outcomes, window)
+                .await
+            {
+                Ok(Some(prediction)) => list(&[prediction]),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        PredictionsSubcommand::List {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_predictions(&broadcaster_id).await {
+                Ok(predictions) => list(&predictions),
+                Err(e) => exit!(1, "An error occurred while fetching the predictions: {}", e),
+            }
+        }
+        PredictionsSubcommand::Lock {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            id,
+        } => {
+            end(
+                &client,
+                broadcaster,
+                broadcaster_id,
+                &id,
+                PredictionStatus::Locked,
+                None,
+            )
+            .await
+        }
+        PredictionsSubcommand::Resolve {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            id,
+            outcome,
+        } => {
+            end(
+                &client,
+                broadcaster,
+                broadcaster_id,
+                &id,
+                PredictionStatus::Resolved,
+                Some(&outcome),
+            )
+            .await
+        }
+        PredictionsSubcommand::Cancel {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            id,
+        } => {
+            end(
+                &client,
+                broadcaster,
+                broadcaster_id,
+                &id,
+                PredictionStatus::Canceled,
+                None,
+            )
+            .await
+        }
+    }
+}
+
+async fn end(
+    client: &ApiClient<'_>,
+    broadcaster: Option<UserName>,
+    broadcaster_id: Option<UserId>,
+    id: &str,
+    status: PredictionStatus,
+    winning_outcome_id: Option<&str>,
+) {
+    let broadcaster_id = get_broadcaster_id_or_die(client, broadcaster, broadcaster_id).await;
+    match client
+        .end_prediction(&broadcaster_id, id, status, winning_outcome_id)
+        .await
+    {
+        Ok(Some(prediction)) => list(&[prediction]),
+        Ok(None) => {}
+        Err(e) => exit!(1, "{}", e),
+    }
+}
+
+fn list(predictions: &[Prediction]) {
+    for prediction in predictions {
+        println!(
+            "{} '{}' {:?}",
+            prediction.id, prediction.title, prediction.status
+        );
+        for outcome in &prediction.outcomes {
+            let winner = if prediction.winning_outcome_id.as_ref() == Some(&outcome.id) {
+                "*"
+            } else {
+                " "
+            };
+            println!(
+                "  {} {} '{}' {} points",
+                winner,
+                outcome.id,
+                outcome.title,
+                outcome.channel_points.unwrap_or_default()
+            );
+        }
+    }
+}
@@ -1,9 +1,16 @@
-use crate::api::{get_broadcaster_id_or_die, ApiClient};
+use crate::api::{
+    get_broadcaster_id_or_die, ApiClient, RewardLimits, RewardLookup, RewardPatch,
+    RewardSpecBuilder,
+};
 use crate::exit;
+use crate::json::{print_json, RewardDto};
 use fuzzy_filter::FuzzyFilter;
 use structopt::StructOpt;
-use twitch_api2::helix::points::{CreateCustomRewardBody, CustomReward, UpdateCustomRewardBody};
-use twitch_api2::types::{UserId, UserName};
+use twitch_api2::helix::points::{
+    CreateCustomRewardBody, CustomReward, CustomRewardRedemption, CustomRewardRedemptionStatus,
+    UpdateCustomRewardBody,
+};
+use twitch_api2::types::{RedemptionId, UserId, UserName};
 
 #[derive(Debug, StructOpt)]
 /// manipulate a streams tags
@@ -24,6 +31,9 @@ pub enum RewardsSubcommand {
         /// print rewards in long format
         #[structopt(short)]
         long: bool,
+        /// print rewards as JSON
+        #[structopt(long, conflicts_with = "long")]
+        json: bool,
         /// string for fuzzy filtering of tags
         filter: Option<String>,
     },
@@ -42,6 +52,82 @@ pub enum RewardsSubcommand {
         #[structopt(flatten)]
         reward: RewardOption,
     },
+    /// deletes a reward of a broadcaster
+    Delete {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// enables a reward of a broadcaster
+    Enable {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// disables a reward of a broadcaster
+    Disable {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// pauses a reward of a broadcaster
+    Pause {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// resumes a paused reward of a broadcaster
+    Resume {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// lists the redemptions of a reward
+    Redemptions {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        /// the status of the redemptions to list
+        ///
+        /// (supported values: unfulfilled, fulfilled, canceled)
+        #[structopt(short, long, default_value = "unfulfilled", parse(try_from_str = parse_redemption_status))]
+        status: CustomRewardRedemptionStatus,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// marks redemptions of a reward as fulfilled
+    Fulfill {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        /// the ids of the redemptions
+        #[structopt(required = true)]
+        redemptions: Vec<RedemptionId>,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// cancels redemptions of a reward, refunding the points to the viewers
+    Refund {
+        /// the title of the reward, matched fuzzily
+        title: String,
+        /// the ids of the redemptions
+        #[structopt(required = true)]
+        redemptions: Vec<RedemptionId>,
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+}
+
+fn parse_redemption_status(s: &str) -> Result<CustomRewardRedemptionStatus, &'static str> {
+    match s {
+        "unfulfilled" => Ok(CustomRewardRedemptionStatus::Unfulfilled),
+        "fulfilled" => Ok(CustomRewardRedemptionStatus::Fulfilled),
+        "canceled" => Ok(CustomRewardRedemptionStatus::Canceled),
+        _ => Err("unsupported redemption status"),
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -120,62 +206,28 @@ impl From<RewardOption> for UpdateCustomRewardBody {
             paused,
             not_paused,
         } = reward;
-        UpdateCustomRewardBody::builder()
-            .title(title)
-            .cost(cost)
-            .prompt(prompt)
-            .is_enabled(match (enabled, disabled) {
-                (true, _) => Some(true),
-                (_, true) => Some(false),
-                _ => None,
-            })
-            .background_color(color)
-            .is_user_input_required(match (user_input, no_user_input) {
-                (true, _) => Some(true),
-                (_, true) => Some(false),
-                _ => None,
-            })
-            .is_max_per_stream_enabled(match max_per_stream {
-                None => None,
-                Some(0) => Some(false),
-                _ => Some(true),
-            })
-            .max_per_stream(if max_per_stream.unwrap_or(0) != 0 {
-                max_per_stream
-            } else {
-                None
-            })
-            .is_max_per_user_per_stream_enabled(match max_per_user {
-                None => None,
-                Some(0) => Some(false),
-                _ => Some(true),
-            })
-            .max_per_user_per_stream(if max_per_user.unwrap_or(0) != 0 {
-                max_per_user
-            } else {
-                None
-            })
-            .is_global_cooldown_enabled(match cooldown {
-                None => None,
-                Some(0) => Some(false),
-                _ => Some(true),
-            })
-            .global_cooldown_seconds(if cooldown.unwrap_or(0) != 0 {
-                cooldown
-            } else {
-                None
-            })
-            .should_redemptions_skip_request_queue(match (auto_fulfill, no_auto_fulfill) {
-                (true, _) => Some(true),
-                (_, true) => Some(false),
-                _ => None,
-            })
-            .is_paused(match (paused, not_paused) {
-                (true, _) => Some(true),
-                (_, true) => Some(false),
-                _ => None,
-            })
-            .build()
+        let flag = |set: bool, unset: bool| match (set, unset) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        };
+        RewardPatch {
+            title,
+            cost,
+            prompt,
+            is_enabled: flag(enabled, disabled),
+            background_color: color,
+            is_user_input_required: flag(user_input, no_user_input),
+            is_paused: flag(paused, not_paused),
+            should_redemptions_skip_request_queue: flag(auto_fulfill, no_auto_fulfill),
+            ..Default::default()
+        }
+        .with_limits(RewardLimits {
+            max_per_stream,
+            max_per_user_per_stream: max_per_user,
+            global_cooldown_seconds: cooldown,
+        })
+        .into()
     }
 }
 impl From<RewardOption> for CreateCustomRewardBody {
@@ -193,33 +245,29 @@ impl From<RewardOption> for CreateCustomRewardBody {
                 cooldown,
                 auto_fulfill,
                 ..
-            } => CreateCustomRewardBody::builder()
-                .title(title)
-                .cost(cost)
-                .prompt(prompt)
-                .is_enabled(!disabled)
-                .background_color(color)
-                .is_user_input_required(user_input)
-                .is_max_per_stream_enabled(max_per_stream.unwrap_or(0) != 0)
-                .max_per_stream(if max_per_stream.unwrap_or(0) != 0 {
-                    max_per_stream
-                } else {
-                    None
-                })
-                .is_max_per_user_per_stream_enabled(max_per_user.unwrap_or(0) != 0)
-                .max_per_user_per_stream(if max_per_user.unwrap_or(0) != 0 {
-                    max_per_user
-                } else {
-                    None
-                })
-                .is_global_cooldown_enabled(cooldown.unwrap_or(0) != 0)
-                .global_cooldown_seconds(if cooldown.unwrap_or(0) != 0 {
-                    cooldown
-                } else {
-                    None
-                })
-                .should_redemptions_skip_request_queue(auto_fulfill)
-                .build(),
+            } => {
+                let mut spec = RewardSpecBuilder::default();
+                spec.title(title)
+                    .cost(cost)
+                    .is_enabled(!disabled)
+                    .is_user_input_required(user_input)
+                    .should_redemptions_skip_request_queue(auto_fulfill);
+                if let Some(prompt) = prompt {
+                    spec.prompt(prompt);
+                }
+                if let Some(color) = color {
+                    spec.background_color(color);
+                }
+                spec.limits(RewardLimits {
+                    max_per_stream,
+                    max_per_user_per_stream: max_per_user,
+                    global_cooldown_seconds: cooldown,
+                });
+                match spec.build() {
+                    Ok(spec) => spec.into(),
+                    Err(e) => exit!(1, "{}", e),
+                }
+            }
             _ => exit!(1, "Title and cost are required to create a new reward."),
         }
     }
@@ -240,6 +288,7 @@ pub async fn rewards(client: ApiClient<'_>, command: RewardsSubcommand) {
     match command {
         RewardsSubcommand::List {
             long,
+            json,
             filter,
             broadcaster:
                 BroadcasterOption {
@@ -250,6 +299,13 @@ pub async fn rewards(client: ApiClient<'_>, command: RewardsSubcommand) {
             let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
             let rewards = client.get_rewards(&id);
             match rewards.await {
+                Ok(rewards) if json => print_json(
+                    &rewards
+                        .iter()
+                        .filter(|r| matches_filter(&r.title, filter.as_deref()))
+                        .map(RewardDto::from)
+                        .collect::<Vec<_>>(),
+                ),
                 Ok(rewards) => list(&rewards, filter, long),
                 Err(e) => exit!(1, "An error occurred while fetching the rewards: {}", e),
             }
@@ -306,6 +362,170 @@ pub async fn rewards(client: ApiClient<'_>, command: RewardsSubcommand) {
                 )
             }
         }
+        RewardsSubcommand::Delete {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            title,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            let CustomReward { id, title, .. } =
+                find_reward_or_die(&client, &broadcaster_id, &title).await;
+
+            match client.delete_custom_reward(&broadcaster_id, &id).await {
+                Ok(_) => println!("Deleted: `{}`", title),
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        RewardsSubcommand::Enable { broadcaster, title } => {
+            set_enabled(&client, broadcaster, &title, true).await
+        }
+        RewardsSubcommand::Disable { broadcaster, title } => {
+            set_enabled(&client, broadcaster, &title, false).await
+        }
+        RewardsSubcommand::Pause { broadcaster, title } => {
+            set_paused(&client, broadcaster, &title, true).await
+        }
+        RewardsSubcommand::Resume { broadcaster, title } => {
+            set_paused(&client, broadcaster, &title, false).await
+        }
+        RewardsSubcommand::Redemptions {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            title,
+            status,
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            let CustomReward { id, .. } =
+                find_reward_or_die(&client, &broadcaster_id, &title).await;
+
+            match client.get_redemptions(&broadcaster_id, &id, status).await {
+                Ok(redemptions) => list_redemptions(&redemptions),
+                Err(e) => exit!(1, "An error occurred while fetching the redemptions: {}", e),
+            }
+        }
+        RewardsSubcommand::Fulfill {
+            broadcaster,
+            title,
+            redemptions,
+        } => {
+            set_redemption_status(
+                &client,
+                broadcaster,
+                &title,
+                &redemptions,
+                CustomRewardRedemptionStatus::Fulfilled,
+            )
+            .await
+        }
+        RewardsSubcommand::Refund {
+            broadcaster,
+            title,
+            redemptions,
+        } => {
+            set_redemption_status(
+                &client,
+                broadcaster,
+                &title,
+                &redemptions,
+                CustomRewardRedemptionStatus::Canceled,
+            )
+            .await
+        }
+    }
+}
+
+async fn set_redemption_status(
+    client: &ApiClient<'_>,
+    BroadcasterOption {
+        broadcaster,
+        broadcaster_id,
+    }: BroadcasterOption,
+    query: &str,
+    redemptions: &[RedemptionId],
+    status: CustomRewardRedemptionStatus,
+) {
+    let broadcaster_id = get_broadcaster_id_or_die(client, broadcaster, broadcaster_id).await;
+    let CustomReward { id, title, .. } = find_reward_or_die(client, &broadcaster_id, query).await;
+
+    match client
+        .update_redemption_status(&broadcaster_id, &id, redemptions, status)
+        .await
+    {
+        Ok(_) => println!("Updated {} redemptions of `{}`", redemptions.len(), title),
+        Err(e) => exit!(1, "{}", e),
+    }
+}
+
+async fn set_enabled(
+    client: &ApiClient<'_>,
+    BroadcasterOption {
+        broadcaster,
+        broadcaster_id,
+    }: BroadcasterOption,
+    query: &str,
+    enabled: bool,
+) {
+    let broadcaster_id = get_broadcaster_id_or_die(client, broadcaster, broadcaster_id).await;
+    let CustomReward { id, title, .. } = find_reward_or_die(client, &broadcaster_id, query).await;
+
+    match client
+        .set_reward_enabled(&broadcaster_id, &id, enabled)
+        .await
+    {
+        Ok(_) if enabled => println!("Enabled: `{}`", title),
+        Ok(_) => println!("Disabled: `{}`", title),
+        Err(e) => exit!(1, "{}", e),
+    }
+}
+
+async fn set_paused(
+    client: &ApiClient<'_>,
+    BroadcasterOption {
+        broadcaster,
+        broadcaster_id,
+    }: BroadcasterOption,
+    query: &str,
+    paused: bool,
+) {
+    let broadcaster_id = get_broadcaster_id_or_die(client, broadcaster, broadcaster_id).await;
+    let CustomReward { id, title, .. } = find_reward_or_die(client, &broadcaster_id, query).await;
+
+    match client.set_reward_paused(&broadcaster_id, &id, paused).await {
+        Ok(_) if paused => println!("Paused: `{}`", title),
+        Ok(_) => println!("Resumed: `{}`", title),
+        Err(e) => exit!(1, "{}", e),
+    }
+}
+
+async fn find_reward_or_die(
+    client: &ApiClient<'_>,
+    broadcaster_id: &UserId,
+    query: &str,
+) -> CustomReward {
+    match client.lookup_reward(broadcaster_id, query).await {
+        Ok(RewardLookup::Found(reward)) => reward,
+        Ok(RewardLookup::NotFound) => exit!(1, "Did not find a reward matching `{}`", query),
+        Ok(RewardLookup::Ambiguous(candidates)) => {
+            let titles: Vec<_> = candidates
+                .iter()
+                .map(|reward| format!("`{}`", reward.title))
+                .collect();
+            exit!(
+                1,
+                "Did not find a unique reward matching `{}`, did you mean {}?",
+                query,
+                titles.join(", ")
+            )
+        }
+        Err(e) => exit!(1, "{}", e),
     }
 }
 
@@ -337,3 +557,28 @@ fn list(rewards: &[CustomReward], filter: Option<String>, long: bool) {
         println!()
     }
 }
+
+fn list_redemptions(redemptions: &[CustomRewardRedemption]) {
+    let max_len = redemptions
+        .iter()
+        .map(|r| r.user_name.as_str().len())
+        .max()
+        .unwrap_or(0);
+
+    for redemption in redemptions {
+        println!(
+            "{} {}{}{}",
+            redemption.id,
+            redemption.user_name,
+            " ".repeat(1 + max_len.saturating_sub(redemption.user_name.as_str().len())),
+            redemption.user_input
+        );
+    }
+}
+
+fn matches_filter(title: &str, filter: Option<&str>) -> bool {
+    match filter {
+        Some(filter) => FuzzyFilter::new(&filter.to_lowercase()).matches(&title.to_lowercase()),
+        None => true,
+    }
+}
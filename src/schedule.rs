@@ -0,0 +1,170 @@
+use crate::api::{get_broadcaster_id_or_die, ApiClient, NewScheduleSegment, ScheduleSegmentPatch};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{CategoryId, UserId, UserName};
+
+#[derive(Debug, StructOpt)]
+/// show or change the stream schedule
+pub struct ScheduleOptions {
+    #[structopt(subcommand)]
+    pub subcommand: ScheduleSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum ScheduleSubcommand {
+    /// lists the upcoming streams of a broadcaster
+    List {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+    },
+    /// adds a stream to the schedule of the token user
+    Add {
+        /// the start, e.g. 2021-07-01T18:00:00Z
+        start_time: String,
+        /// the time zone, e.g. Europe/Berlin
+        timezone: String,
+        /// the length in minutes
+        #[structopt(short, long, default_value = "240")]
+        duration: u32,
+        /// repeat the stream every week
+        #[structopt(short, long)]
+        recurring: bool,
+        /// the id of the category
+        #[structopt(short, long)]
+        category_id: Option<CategoryId>,
+        /// the title of the stream
+        #[structopt(short, long)]
+        title: Option<String>,
+    },
+    /// changes a stream in the schedule of the token user
+    Update {
+        /// the id of the segment
+        id: String,
+        /// the new start
+        #[structopt(long)]
+        start_time: Option<String>,
+        /// the new time zone
+        #[structopt(long)]
+        timezone: Option<String>,
+        /// the new length in minutes
+        #[structopt(short, long)]
+        duration: Option<u32>,
+        /// the id of the new category
+        #[structopt(short, long)]
+        category_id: Option<CategoryId>,
+        /// the new title
+        #[structopt(short, long)]
+        title: Option<String>,
+        /// cancel or restore the stream
+        #[structopt(long)]
+        canceled: Option<bool>,
+    },
+    /// removes a stream from the schedule of the token user
+    Delete {
+        /// the id of the segment
+        id: String,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn schedule(client: ApiClient<'_>, command: ScheduleSubcommand) {
+    match command {
+        ScheduleSubcommand::List {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+        } => {
+            let broadcaster_id =
+                get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_schedule(&broadcaster_id).await {
+                Ok(segments) => {
+                    for segment in segments {
+                        println!(
+                            "{} {} - {} '{}' {}{}",
+                            segment.id,
+                            segment.start_time,
+                            segment.end_time,
+                            segment.title,
+                            segment.category.map(|c| c.name).unwrap_or_default(),
+                            if segment.canceled_until.is_some() {
+                                " (canceled)"
+                            } else {
+                                ""
+                            }
+                        );
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while fetching the schedule: {}", e),
+            }
+        }
+        ScheduleSubcommand::Add {
+            start_time,
+            timezone,
+            duration,
+            recurring,
+            category_id,
+            title,
+        } => {
+            let broadcaster_id = get_broadcaster_id_or_die(&client, None, None).await;
+            let segment = NewScheduleSegment {
+                start_time,
+                timezone,
+                duration,
+                is_recurring: recurring,
+                category_id,
+                title,
+            };
+            match client
+                .create_schedule_segment(&broadcaster_id, segment)
+                .await
+            {
+                Ok(Some(segment)) => println!("Added segment `{}`", segment.id),
+                Ok(None) => {}
+                Err(e) => exit!(1, "{}", e),
+            }
+        }
+        ScheduleSubcommand::Update {
+            id,
+            start_time,
+            timezone,
+            duration,
+            category_id,
+            title,
+            canceled,
+        } => {
+            let broadcaster_id = get_broadcaster_id_or_die(&client, None, None).await;
+            let patch = ScheduleSegmentPatch {
+                start_time,
+                timezone,
+                duration,
+                category_id,
+                title,
+                is_canceled: canceled,
+            };
+            if let Err(e) = client
+                .update_schedule_segment(&broadcaster_id, &id, patch)
+                .await
+            {
+                exit!(1, "{}", e);
+            }
+        }
+        ScheduleSubcommand::Delete { id } => {
+            let broadcaster_id = get_broadcaster_id_or_die(&client, None, None).await;
+            if let Err(e) = client.delete_schedule_segment(&broadcaster_id, &id).await {
+                exit!(1, "{}", e);
+            }
+        }
+    }
+}
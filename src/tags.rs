@@ -1,4 +1,4 @@
-use crate::api::{ApiClient, get_broadcaster_id_or_die};
+use crate::api::{get_broadcaster_id_or_die, ApiClient};
 use crate::{exit, warning};
 use fuzzy_filter::FuzzyFilter;
 use structopt::StructOpt;
@@ -123,7 +123,10 @@ pub async fn tags(client: ApiClient<'_>, locale: &str, command: TagsSubcommand)
                 }
             };
 
-            let mut new_tags = match client.get_tag_ids_matching(tags.as_slice(), locale).await {
+            let (mut new_tags, unmatched) = match client
+                .resolve_tag_names(tags.as_slice(), Some(&[locale, "en-us"][..]))
+                .await
+            {
                 Ok(tags) => tags,
                 Err(_) => exit!(
                     1,
@@ -132,10 +135,11 @@ pub async fn tags(client: ApiClient<'_>, locale: &str, command: TagsSubcommand)
                 ),
             };
 
-            if new_tags.len() != tags.len() {
+            if !unmatched.is_empty() {
                 warning!(
-                    "Could not convert all tags to tag ids. \
-                    Check if all Tags are set correctly with `twitchctl tags list`"
+                    "Could not find the tags `{}`. \
+                    Check if all Tags are set correctly with `twitchctl tags list`",
+                    unmatched.join("`, `")
                 );
             }
 
@@ -0,0 +1,92 @@
+use crate::api::{get_broadcaster_id_or_die, ApiClient, Video, VideoType};
+use crate::exit;
+use structopt::StructOpt;
+use twitch_api2::types::{UserId, UserName, VideoId};
+
+#[derive(Debug, StructOpt)]
+/// list or delete videos
+pub struct VideosOptions {
+    #[structopt(subcommand)]
+    pub subcommand: VideosSubcommand,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum VideosSubcommand {
+    /// lists the videos of a broadcaster
+    List {
+        #[structopt(flatten)]
+        broadcaster: BroadcasterOption,
+        /// only list videos of this type
+        ///
+        /// (supported values: archive, highlight, upload)
+        #[structopt(short = "t", long = "type")]
+        video_type: Option<VideoType>,
+    },
+    /// shows a video
+    Get {
+        /// the id of the video
+        id: VideoId,
+    },
+    /// deletes videos of the token user
+    Delete {
+        /// the ids of the videos
+        #[structopt(required = true)]
+        ids: Vec<VideoId>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+pub struct BroadcasterOption {
+    /// the name of the broadcaster
+    #[structopt(short, long, conflicts_with = "broadcaster_id")]
+    broadcaster: Option<UserName>,
+
+    /// the id of the broadcaster
+    #[structopt(long)]
+    broadcaster_id: Option<UserId>,
+}
+
+pub async fn videos(client: ApiClient<'_>, command: VideosSubcommand) {
+    match command {
+        VideosSubcommand::List {
+            broadcaster:
+                BroadcasterOption {
+                    broadcaster,
+                    broadcaster_id,
+                },
+            video_type,
+        } => {
+            let id = get_broadcaster_id_or_die(&client, broadcaster, broadcaster_id).await;
+            match client.get_videos(&id, video_type).await {
+                Ok(videos) => {
+                    for video in videos {
+                        print_video(&video);
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while fetching the videos: {}", e),
+            }
+        }
+        VideosSubcommand::Get { id } => match client.get_video_by_id(&id).await {
+            Ok(Some(video)) => print_video(&video),
+            Ok(None) => exit!(1, "No video with id `{}` found", id),
+            Err(e) => exit!(1, "An error occurred while fetching the video: {}", e),
+        },
+        VideosSubcommand::Delete { ids } => {
+            match client.delete_videos(&ids.iter().collect::<Vec<_>>()).await {
+                Ok(deleted) => {
+                    for id in deleted {
+                        println!("Deleted video `{}`", id);
+                    }
+                }
+                Err(e) => exit!(1, "An error occurred while deleting the videos: {}", e),
+            }
+        }
+    }
+}
+
+fn print_video(video: &Video) {
+    println!(
+        "{} {} {:>9} {:>6} views '{}' {}",
+        video.id, video.created_at, video.duration, video.view_count, video.title, video.url
+    );
+}